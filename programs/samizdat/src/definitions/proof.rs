@@ -1,15 +1,26 @@
+use crate::errors::SamizdatError;
 use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
 
 pub const PROOF_DOMAIN_V1: &[u8] = b"samizdat:proof:v1";
+pub const PROOF_DOMAIN_V2: &[u8] = b"samizdat:proof:v2";
+
+// Ed25519 precompile instruction layout
+// [num_signatures: u8][padding: u8][offsets: 14 bytes per signature][data...]
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+/// Instruction index value meaning "data lives in the Ed25519 instruction itself".
+const ED25519_SAME_INSTRUCTION: u16 = u16::MAX;
 
 /// Canonical payload the renderer signs off-chain.
 /// The program reconstructs this from instruction inputs
-/// and verifies the signature against ScreenAccount.signing_key.
+/// and verifies the signature against NodeAccount.signing_key.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub struct ProofPayloadV1 {
-    pub ad: Pubkey,
-    pub screen: Pubkey,
-    pub nonce: u64,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub nonce: i64,
     pub timestamp: i64,
 }
 
@@ -18,10 +29,102 @@ impl ProofPayloadV1 {
     pub fn to_signable_bytes(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(PROOF_DOMAIN_V1.len() + 80);
         buf.extend_from_slice(PROOF_DOMAIN_V1);
-        buf.extend_from_slice(&self.ad.to_bytes());
-        buf.extend_from_slice(&self.screen.to_bytes());
+        buf.extend_from_slice(&self.campaign.to_bytes());
+        buf.extend_from_slice(&self.node.to_bytes());
+        buf.extend_from_slice(&self.nonce.to_le_bytes());
+        buf.extend_from_slice(&self.timestamp.to_le_bytes());
+        buf
+    }
+
+    /// Require the preceding Ed25519 instruction to sign this payload with `signing_key`.
+    pub fn verify(&self, instructions_sysvar: &AccountInfo, signing_key: &Pubkey) -> Result<()> {
+        verify_ed25519_message(instructions_sysvar, signing_key, &self.to_signable_bytes())
+    }
+}
+
+/// Proof for PerSecond campaigns: V1 plus the displayed duration, so the
+/// renderer vouches for how long the creative was on screen.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProofPayloadV2 {
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub nonce: i64,
+    pub timestamp: i64,
+    pub duration_ms: u32,
+}
+
+impl ProofPayloadV2 {
+    /// Serialize domain + payload into a signable message buffer.
+    pub fn to_signable_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(PROOF_DOMAIN_V2.len() + 84);
+        buf.extend_from_slice(PROOF_DOMAIN_V2);
+        buf.extend_from_slice(&self.campaign.to_bytes());
+        buf.extend_from_slice(&self.node.to_bytes());
         buf.extend_from_slice(&self.nonce.to_le_bytes());
         buf.extend_from_slice(&self.timestamp.to_le_bytes());
+        buf.extend_from_slice(&self.duration_ms.to_le_bytes());
         buf
     }
+
+    /// Require the preceding Ed25519 instruction to sign this payload with `signing_key`.
+    pub fn verify(&self, instructions_sysvar: &AccountInfo, signing_key: &Pubkey) -> Result<()> {
+        verify_ed25519_message(instructions_sysvar, signing_key, &self.to_signable_bytes())
+    }
+}
+
+/// Require the instruction immediately preceding the current one to be an
+/// Ed25519 program instruction in which `signing_key` signs `expected`.
+///
+/// The Ed25519 precompile has already checked the signature itself by the
+/// time this program runs; we only need to bind its key and message.
+fn verify_ed25519_message(
+    instructions_sysvar: &AccountInfo,
+    signing_key: &Pubkey,
+    expected: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, SamizdatError::InvalidProof);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        solana_sdk_ids::ed25519_program::ID,
+        SamizdatError::InvalidProof
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_LEN && data[0] == 1,
+        SamizdatError::InvalidProof
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = ED25519_OFFSETS_START;
+    let signature_ix_index = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // Signature, key and message must all live in the Ed25519 instruction,
+    // otherwise the precompile may have verified different bytes.
+    require!(
+        signature_ix_index == ED25519_SAME_INSTRUCTION
+            && pubkey_ix_index == ED25519_SAME_INSTRUCTION
+            && message_ix_index == ED25519_SAME_INSTRUCTION,
+        SamizdatError::InvalidProof
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN)
+        .ok_or(SamizdatError::InvalidProof)?;
+    require!(pubkey == signing_key.as_ref(), SamizdatError::InvalidProof);
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(SamizdatError::InvalidProof)?;
+    require!(message == expected, SamizdatError::InvalidProof);
+
+    Ok(())
 }
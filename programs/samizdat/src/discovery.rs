@@ -0,0 +1,225 @@
+use crate::errors::SamizdatError;
+use crate::state::{GeoIndex, GEO_INDEX_SEED, MAX_GEO_INDEX_CAMPAIGNS};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+
+/// Move `campaign` from the GeoIndex PDAs of `old_cells` to those of
+/// `new_cells`, returning the sorted cells whose index lists it afterwards.
+/// `index_accounts` are the PDAs of the cells that change, in sorted cell
+/// order. The payer funds new and growing indexes and receives the rent back
+/// as indexes shrink or close.
+///
+/// A full index leaves the campaign out of that cell instead of failing, and
+/// a removal never fails on the index's contents, so leaving discovery only
+/// needs the right accounts.
+pub fn update_geo_indexes<'info>(
+    campaign: Pubkey,
+    old_cells: &[String],
+    new_cells: &[String],
+    index_accounts: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: Option<&AccountInfo<'info>>,
+) -> Result<Vec<String>> {
+    let mut changes: Vec<(&String, bool)> = old_cells
+        .iter()
+        .filter(|cell| !new_cells.contains(cell))
+        .map(|cell| (cell, false))
+        .chain(
+            new_cells
+                .iter()
+                .filter(|cell| !old_cells.contains(cell))
+                .map(|cell| (cell, true)),
+        )
+        .collect();
+    changes.sort();
+    require!(
+        index_accounts.len() == changes.len(),
+        SamizdatError::InvalidGeoIndex
+    );
+
+    let mut listed: Vec<String> = old_cells
+        .iter()
+        .filter(|cell| new_cells.contains(cell))
+        .cloned()
+        .collect();
+    for ((cell, add), index_info) in changes.into_iter().zip(index_accounts) {
+        require!(index_info.is_writable, SamizdatError::InvalidGeoIndex);
+        let (expected, bump) =
+            Pubkey::find_program_address(&[GEO_INDEX_SEED, cell.as_bytes()], &crate::ID);
+        require_keys_eq!(index_info.key(), expected, SamizdatError::InvalidGeoIndex);
+        if add {
+            let system_program = system_program.ok_or(SamizdatError::InvalidGeoIndex)?;
+            if add_to_index(campaign, cell, bump, index_info, payer, system_program)? {
+                listed.push(cell.clone());
+            }
+        } else {
+            remove_from_index(campaign, cell, index_info, payer)?;
+        }
+    }
+    listed.sort();
+    Ok(listed)
+}
+
+/// List `campaign` in the index at `index_info`, or return false if the
+/// index is already full.
+fn add_to_index<'info>(
+    campaign: Pubkey,
+    cell: &str,
+    bump: u8,
+    index_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<bool> {
+    // First campaign in the cell: create the index
+    if index_info.owner == &system_program::ID {
+        create_index(
+            index_info,
+            payer,
+            system_program,
+            &[GEO_INDEX_SEED, cell.as_bytes(), &[bump]],
+        )?;
+        write_index(
+            index_info,
+            &GeoIndex {
+                cell: cell.to_string(),
+                campaigns: vec![campaign],
+                bump,
+            },
+        )?;
+        return Ok(true);
+    }
+
+    let mut index = load_index(cell, index_info)?;
+    if index.campaigns.len() >= MAX_GEO_INDEX_CAMPAIGNS {
+        return Ok(false);
+    }
+    index.campaigns.push(campaign);
+    let space = GeoIndex::space(index.campaigns.len());
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(index_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: index_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    index_info.resize(space)?;
+    write_index(index_info, &index)?;
+    Ok(true)
+}
+
+fn remove_from_index<'info>(
+    campaign: Pubkey,
+    cell: &str,
+    index_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+) -> Result<()> {
+    // Nothing to undo if the index is gone, unreadable or does not list the
+    // campaign; the campaign still leaves discovery
+    let Ok(mut index) = load_index(cell, index_info) else {
+        return Ok(());
+    };
+    let Some(position) = index.campaigns.iter().position(|key| key == &campaign) else {
+        return Ok(());
+    };
+    index.campaigns.swap_remove(position);
+
+    // Last campaign out closes the index
+    if index.campaigns.is_empty() {
+        let lamports = index_info.lamports();
+        **index_info.try_borrow_mut_lamports()? -= lamports;
+        **payer.try_borrow_mut_lamports()? += lamports;
+        index_info.resize(0)?;
+        index_info.assign(&system_program::ID);
+        return Ok(());
+    }
+
+    let space = GeoIndex::space(index.campaigns.len());
+    write_index(index_info, &index)?;
+    index_info.resize(space)?;
+    let excess = index_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(space));
+    **index_info.try_borrow_mut_lamports()? -= excess;
+    **payer.try_borrow_mut_lamports()? += excess;
+    Ok(())
+}
+
+/// Create the index PDA, topping up any lamports already sent to it.
+fn create_index<'info>(
+    index_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let space = GeoIndex::space(1);
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer_seeds = &[seeds];
+    if index_info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: index_info.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let shortfall = rent.saturating_sub(index_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: index_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: index_info.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: index_info.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
+
+fn load_index(cell: &str, index_info: &AccountInfo) -> Result<GeoIndex> {
+    require_keys_eq!(*index_info.owner, crate::ID, SamizdatError::InvalidGeoIndex);
+    let index = GeoIndex::try_deserialize(&mut &index_info.try_borrow_data()?[..])?;
+    require!(index.cell == cell, SamizdatError::InvalidGeoIndex);
+    Ok(index)
+}
+
+fn write_index(index_info: &AccountInfo, index: &GeoIndex) -> Result<()> {
+    index.try_serialize(&mut &mut index_info.try_borrow_mut_data()?[..])
+}
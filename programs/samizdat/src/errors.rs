@@ -5,8 +5,8 @@ pub enum SamizdatError {
     #[msg("Invalid campaign ID")]
     InvalidCampaignId,
 
-    #[msg("Too many CIDs (max 10)")]
-    TooManyCids,
+    #[msg("Too many creatives (max 10)")]
+    TooManyCreatives,
 
     #[msg("Bounty per play must be > 0")]
     InvalidBounty,
@@ -53,14 +53,14 @@ pub enum SamizdatError {
     #[msg("Publisher account does not match")]
     PublisherMismatch,
 
-    #[msg("Invalid CID: empty or exceeds max length")]
-    InvalidCid,
+    #[msg("Invalid creative: bad CID, mime type, duration or size")]
+    InvalidCreative,
 
     #[msg("Amount must be greater than zero")]
     InvalidAmount,
 
-    #[msg("Invalid CID index")]
-    InvalidCidIndex,
+    #[msg("Invalid creative index")]
+    InvalidCreativeIndex,
 
     #[msg("Invalid status transition")]
     InvalidStatusTransition,
@@ -70,4 +70,175 @@ pub enum SamizdatError {
 
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+
+    #[msg("Proof-of-play signature missing or invalid")]
+    InvalidProof,
+
+    #[msg("Proof timestamp outside the claim window")]
+    InvalidProofTimestamp,
+
+    #[msg("Token accounts are required for SPL campaigns")]
+    MissingTokenAccounts,
+
+    #[msg("Token mint does not match campaign")]
+    MintMismatch,
+
+    #[msg("Flight end must be after flight start and in the future")]
+    InvalidFlightWindow,
+
+    #[msg("Campaign flight has not started yet")]
+    CampaignNotStarted,
+
+    #[msg("Campaign flight has ended")]
+    CampaignEnded,
+
+    #[msg("Campaign flight has not ended yet")]
+    FlightNotEnded,
+
+    #[msg("Daypart UTC offset out of range")]
+    InvalidDaypart,
+
+    #[msg("Campaign is not scheduled to run at this hour")]
+    OutsideDaypart,
+
+    #[msg("Campaign has reached its daily play cap")]
+    DailyCapReached,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Fee exceeds 10000 basis points")]
+    InvalidFee,
+
+    #[msg("Play timeout must be > 0")]
+    InvalidTimeout,
+
+    #[msg("Treasury account does not match protocol config")]
+    TreasuryMismatch,
+
+    #[msg("Claim nonce already used or too old")]
+    NonceAlreadyUsed,
+
+    #[msg("Play record is not settled")]
+    PlayNotSettled,
+
+    #[msg("Reputation exceeds 10000 basis points")]
+    InvalidReputation,
+
+    #[msg("Node resolution is below the creative's minimum")]
+    ResolutionTooLow,
+
+    #[msg("Pricing parameters must be > 0")]
+    InvalidPricing,
+
+    #[msg("Played duration missing or exceeds the creative or campaign maximum")]
+    InvalidDuration,
+
+    #[msg("Auction window must satisfy now < commit end < reveal end <= slot start < slot end")]
+    InvalidAuctionWindow,
+
+    #[msg("Auction is not open for bids")]
+    AuctionNotOpen,
+
+    #[msg("Commit window has closed")]
+    BiddingClosed,
+
+    #[msg("Bidding has not closed yet")]
+    BiddingNotClosed,
+
+    #[msg("Bid below reserve price or above the campaign's bounty")]
+    InvalidBid,
+
+    #[msg("Not within the reveal window")]
+    RevealNotOpen,
+
+    #[msg("Revealed amount and salt do not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Bid already revealed")]
+    BidAlreadyRevealed,
+
+    #[msg("Booking needs a future window, guaranteed plays and a price > 0")]
+    InvalidBooking,
+
+    #[msg("Booking is not in the required status")]
+    InvalidBookingStatus,
+
+    #[msg("Node already has a booking overlapping this window")]
+    BookingOverlap,
+
+    #[msg("Node has too many open bookings")]
+    TooManyBookings,
+
+    #[msg("Node is booked for another campaign, or the booking was not passed")]
+    NodeBooked,
+
+    #[msg("Booking is not active at this time")]
+    BookingNotActive,
+
+    #[msg("Booking has no guaranteed plays left to claim")]
+    BookingFulfilled,
+
+    #[msg("Booking account does not match the play record")]
+    BookingMismatch,
+
+    #[msg("Booking window has not passed yet, or a booked play is still unconfirmed")]
+    BookingNotEnded,
+
+    #[msg("Campaign did not win this slot")]
+    NotAuctionWinner,
+
+    #[msg("Slot is not settled or outside its claim window")]
+    SlotNotClaimable,
+
+    #[msg("Auction still has open bids or an unfinished slot")]
+    AuctionNotFinished,
+
+    #[msg("Play pays less than the node's minimum bounty")]
+    BountyBelowNodeFloor,
+
+    #[msg("Geo region out of range, too large or malformed")]
+    InvalidGeoRegion,
+
+    #[msg("GeoIndex accounts missing, out of order or invalid")]
+    InvalidGeoIndex,
+
+    #[msg("Node is not on the campaign's allowlist")]
+    NodeNotAllowlisted,
+
+    #[msg("Node or its operator is on the campaign's denylist")]
+    NodeDenylisted,
+
+    #[msg("Node denylist is full")]
+    TooManyDenylistEntries,
+
+    #[msg("Node requires its operator's approval of this creative")]
+    CampaignNotApproved,
+
+    #[msg("Campaign has no creative with this content hash")]
+    CreativeNotFound,
+
+    #[msg("Frequency cap must allow 1 to 24 plays over a positive window")]
+    InvalidFrequencyCap,
+
+    #[msg("Publisher's frequency cap for this node reached")]
+    FrequencyCapReached,
+
+    #[msg("Node has too many open slot auctions")]
+    TooManyAuctionedSlots,
+
+    #[msg("Slot overlaps a booking or another auctioned slot on the node")]
+    SlotOverlap,
+
+    #[msg("Node's current slot was auctioned; pass its SlotAuction")]
+    SlotAuctioned,
+
+    #[msg("Claim nonce is too far above the highest used nonce")]
+    NonceTooFarAhead,
+
+    #[msg("Campaign already has a creative with this content hash")]
+    DuplicateCreative,
+
+    #[msg("Auction winner can still claim the slot")]
+    WinnerCanStillClaim,
 }
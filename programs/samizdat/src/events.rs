@@ -0,0 +1,377 @@
+use crate::state::{
+    AuctionStatus, CampaignStatus, Creative, FrequencyCap, GeoLocation, NodeStatus, PlayStatus,
+    PricingModel, ScreenSize,
+};
+use anchor_lang::prelude::*;
+
+// Events are emitted through `emit_cpi!` so they are recorded as inner
+// instructions and survive program log truncation.
+
+// Publisher Events
+#[event]
+pub struct PublisherRegistered {
+    pub publisher: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FrequencyCapUpdated {
+    pub publisher: Pubkey,
+    pub node_cooldown: i64,
+    pub frequency_cap: Option<FrequencyCap>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignCreated {
+    pub campaign: Pubkey,
+    pub publisher: Pubkey,
+    pub campaign_id: u64,
+    pub mint: Option<Pubkey>,
+    pub bounty_per_play: u64,
+    pub pricing: PricingModel,
+    pub total_plays: u64,
+    pub funded_amount: u64,
+    pub start_at: Option<i64>,
+    pub end_at: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignFunded {
+    pub campaign: Pubkey,
+    pub amount: u64,
+    pub status: CampaignStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignUpdated {
+    pub campaign: Pubkey,
+    pub tag_mask: u64,
+    pub status: CampaignStatus,
+    pub start_at: Option<i64>,
+    pub end_at: Option<i64>,
+    pub daily_play_cap: Option<u64>,
+    pub bounty_per_play: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreativesAdded {
+    pub campaign: Pubkey,
+    pub creatives: Vec<Creative>,
+    pub total_creatives: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when `close_campaign` has to wait for outstanding claims.
+#[event]
+pub struct CampaignDraining {
+    pub campaign: Pubkey,
+    pub outstanding_claims: u64,
+    pub open_bookings: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignClosed {
+    pub campaign: Pubkey,
+    pub publisher: Pubkey,
+    /// Unspent budget returned to the publisher (tokens or lamports above rent)
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignExpired {
+    pub campaign: Pubkey,
+    pub end_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NodeDenylistUpdated {
+    pub node_denylist: Pubkey,
+    pub campaign: Pubkey,
+    /// Entries after the update
+    pub entries: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NodeDenylistClosed {
+    pub node_denylist: Pubkey,
+    pub campaign: Pubkey,
+    pub timestamp: i64,
+}
+
+// Operator Events
+#[event]
+pub struct NodeRegistered {
+    pub node: Pubkey,
+    pub authority: Pubkey,
+    pub node_id: u64,
+    pub signing_key: Pubkey,
+    pub location: GeoLocation,
+    pub screen_size: ScreenSize,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NodeUpdated {
+    pub node: Pubkey,
+    pub location: GeoLocation,
+    pub estimated_footfall: u32,
+    pub blocked_tag_mask: u64,
+    pub required_tag_mask: u64,
+    pub min_bounty_per_play: u64,
+    pub requires_approval: bool,
+    pub status: NodeStatus,
+    pub signing_key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignApproved {
+    pub approved_campaign: Pubkey,
+    pub node: Pubkey,
+    pub campaign: Pubkey,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignApprovalRevoked {
+    pub approved_campaign: Pubkey,
+    pub node: Pubkey,
+    pub campaign: Pubkey,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+// Play Cycle Events
+#[event]
+pub struct PlayClaimed {
+    pub play_record: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub nonce: i64,
+    pub creative_index: u8,
+    pub bond_amount: u64,
+    pub fixed_price: Option<u64>,
+    pub booking: Option<Pubkey>,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct PlayConfirmed {
+    pub play_record: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub operator: Pubkey,
+    pub payment_amount: u64,
+    pub fee_amount: u64,
+    pub duration_ms: u32,
+    pub proof_timestamp: i64,
+    pub confirmed_at: i64,
+}
+
+#[event]
+pub struct PlayTimedOut {
+    pub play_record: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub cranker: Pubkey,
+    pub crank_reward: u64,
+    pub bond_forfeited: u64,
+    pub claimed_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an operator reclaims rent from a settled PlayRecord.
+#[event]
+pub struct PlayRecordClosed {
+    pub play_record: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub status: PlayStatus,
+    pub timestamp: i64,
+}
+
+// Slot Auction Events
+#[event]
+pub struct SlotAuctionOpened {
+    pub slot_auction: Pubkey,
+    pub node: Pubkey,
+    pub slot_id: u64,
+    pub commit_ends_at: i64,
+    pub reveal_ends_at: i64,
+    pub slot_start: i64,
+    pub slot_end: i64,
+    pub reserve_price: u64,
+    pub bid_deposit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidCommitted {
+    pub bid: Pubkey,
+    pub slot_auction: Pubkey,
+    pub campaign: Pubkey,
+    pub deposit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidRevealed {
+    pub bid: Pubkey,
+    pub slot_auction: Pubkey,
+    pub campaign: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlotAuctionSettled {
+    pub slot_auction: Pubkey,
+    pub winner: Option<Pubkey>,
+    pub clearing_price: u64,
+    pub bid_count: u32,
+    pub revealed_bids: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionedSlotReleased {
+    pub slot_auction: Pubkey,
+    pub node: Pubkey,
+    pub winner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidClosed {
+    pub bid: Pubkey,
+    pub slot_auction: Pubkey,
+    pub campaign: Pubkey,
+    pub deposit_forfeited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlotAuctionClosed {
+    pub slot_auction: Pubkey,
+    pub node: Pubkey,
+    pub status: AuctionStatus,
+    pub timestamp: i64,
+}
+
+// Booking Events
+#[event]
+pub struct BookingCreated {
+    pub booking: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub booking_id: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub guaranteed_plays: u64,
+    pub price_per_play: u64,
+    pub escrowed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BookingAccepted {
+    pub booking: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BookingCancelled {
+    pub booking: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    /// Escrow returned to the campaign's unreserved budget
+    pub released: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BookingSettled {
+    pub booking: Pubkey,
+    pub campaign: Pubkey,
+    pub node: Pubkey,
+    pub guaranteed_plays: u64,
+    pub plays_delivered: u64,
+    /// Escrow for undelivered plays returned to the campaign's unreserved budget
+    pub released: u64,
+    pub timestamp: i64,
+}
+
+// Admin Events
+/// Emitted on initialization and on every config update with the resulting values.
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub crank_reward_bps: u16,
+    pub claim_bond: u64,
+    pub play_timeout: i64,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolAdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolAdminAccepted {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PublisherSuspended {
+    pub publisher: Pubkey,
+    pub reason: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PublisherReinstated {
+    pub publisher: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NodeSuspended {
+    pub node: Pubkey,
+    pub reason: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NodeReinstated {
+    pub node: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NodeDisputeLost {
+    pub node: Pubkey,
+    pub reason: u16,
+    pub disputes_lost: u64,
+    pub reputation_bps: u16,
+    pub timestamp: i64,
+}
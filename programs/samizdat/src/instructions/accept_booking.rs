@@ -0,0 +1,70 @@
+use crate::errors::SamizdatError;
+use crate::events::BookingAccepted;
+use crate::state::{
+    BookedWindow, Booking, BookingStatus, NodeAccount, NodeStatus, BOOKING_SEED, NODE_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptBooking<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BOOKING_SEED,
+            booking.campaign_account.as_ref(),
+            node_account.key().as_ref(),
+            &booking.booking_id.to_le_bytes(),
+        ],
+        bump = booking.bump,
+        has_one = node_account,
+    )]
+    pub booking: Account<'info, Booking>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn process_accept_booking(ctx: Context<AcceptBooking>) -> Result<()> {
+    let booking = &ctx.accounts.booking;
+    require!(
+        booking.status == BookingStatus::Proposed,
+        SamizdatError::InvalidBookingStatus
+    );
+    require!(
+        ctx.accounts.node_account.status == NodeStatus::Active,
+        SamizdatError::NodeNotActive
+    );
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < booking.end_at,
+        SamizdatError::BookingNotActive
+    );
+
+    // Reserve the window on the node
+    let window = BookedWindow {
+        booking: booking.key(),
+        start_at: booking.start_at,
+        end_at: booking.end_at,
+    };
+    ctx.accounts.node_account.add_booking(window)?;
+
+    let booking = &mut ctx.accounts.booking;
+    booking.status = BookingStatus::Active;
+
+    emit_cpi!(BookingAccepted {
+        booking: booking.key(),
+        campaign: booking.campaign_account,
+        node: booking.node_account,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::errors::SamizdatError;
+use crate::events::ProtocolAdminAccepted;
+use crate::state::{ProtocolConfig, PROTOCOL_CONFIG_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptProtocolAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.pending_admin == Some(pending_admin.key()) @ SamizdatError::Unauthorized,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub pending_admin: Signer<'info>,
+}
+
+pub fn process_accept_protocol_admin(ctx: Context<AcceptProtocolAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    let previous_admin = config.admin;
+    config.admin = ctx.accounts.pending_admin.key();
+    config.pending_admin = None;
+
+    emit_cpi!(ProtocolAdminAccepted {
+        previous_admin,
+        admin: config.admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,12 +1,14 @@
 use crate::errors::SamizdatError;
+use crate::events::CreativesAdded;
 use crate::state::{
-    CampaignAccount, CampaignStatus, PublisherAccount, CAMPAIGN_SEED, MAX_CIDS, MAX_CID_LENGTH,
+    CampaignAccount, CampaignStatus, Creative, PublisherAccount, CAMPAIGN_SEED, MAX_CREATIVES,
     PUBLISHER_SEED,
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct AddCidsToCampaign<'info> {
+pub struct AddCreativesToCampaign<'info> {
     #[account(
         mut,
         seeds = [CAMPAIGN_SEED, publisher_account.key().as_ref(), &campaign_account.campaign_id.to_le_bytes()],
@@ -25,9 +27,9 @@ pub struct AddCidsToCampaign<'info> {
     pub authority: Signer<'info>,
 }
 
-pub fn process_add_cids_to_campaign(
-    ctx: Context<AddCidsToCampaign>,
-    new_cids: Vec<String>,
+pub fn process_add_creatives_to_campaign(
+    ctx: Context<AddCreativesToCampaign>,
+    new_creatives: Vec<Creative>,
 ) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign_account;
 
@@ -36,17 +38,22 @@ pub fn process_add_cids_to_campaign(
         SamizdatError::CampaignNotActive
     );
     require!(
-        campaign.cids.len() + new_cids.len() <= MAX_CIDS,
-        SamizdatError::TooManyCids
+        campaign.creatives.len() + new_creatives.len() <= MAX_CREATIVES,
+        SamizdatError::TooManyCreatives
     );
-    for cid in &new_cids {
-        require!(
-            !cid.is_empty() && cid.len() <= MAX_CID_LENGTH,
-            SamizdatError::InvalidCid
-        );
+    for creative in &new_creatives {
+        creative.validate()?;
     }
 
-    campaign.cids.extend(new_cids);
+    campaign.creatives.extend(new_creatives.iter().cloned());
+    Creative::require_distinct_hashes(&campaign.creatives)?;
+
+    emit_cpi!(CreativesAdded {
+        campaign: campaign.key(),
+        creatives: new_creatives,
+        total_creatives: campaign.creatives.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
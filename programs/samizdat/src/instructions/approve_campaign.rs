@@ -0,0 +1,79 @@
+use crate::errors::SamizdatError;
+use crate::events::CampaignApproved;
+use crate::state::{
+    ApprovedCampaign, CampaignAccount, NodeAccount, APPROVED_CAMPAIGN_SEED, CAMPAIGN_SEED,
+    NODE_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(content_hash: [u8; 32])]
+pub struct ApproveCampaign<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ApprovedCampaign::INIT_SPACE,
+        seeds = [
+            APPROVED_CAMPAIGN_SEED,
+            node_account.key().as_ref(),
+            campaign_account.key().as_ref(),
+            &content_hash,
+        ],
+        bump,
+    )]
+    pub approved_campaign: Account<'info, ApprovedCampaign>,
+
+    #[account(
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(
+        seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_approve_campaign(
+    ctx: Context<ApproveCampaign>,
+    content_hash: [u8; 32],
+) -> Result<()> {
+    // Approvals are per creative, so only a creative the campaign carries
+    // can be approved
+    require!(
+        ctx.accounts
+            .campaign_account
+            .creatives
+            .iter()
+            .any(|creative| creative.content_hash == content_hash),
+        SamizdatError::CreativeNotFound
+    );
+
+    let clock = Clock::get()?;
+    ctx.accounts.approved_campaign.set_inner(ApprovedCampaign {
+        node_account: ctx.accounts.node_account.key(),
+        campaign_account: ctx.accounts.campaign_account.key(),
+        content_hash,
+        approved_at: clock.unix_timestamp,
+        bump: ctx.bumps.approved_campaign,
+    });
+
+    emit_cpi!(CampaignApproved {
+        approved_campaign: ctx.accounts.approved_campaign.key(),
+        node: ctx.accounts.node_account.key(),
+        campaign: ctx.accounts.campaign_account.key(),
+        content_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::errors::SamizdatError;
+use crate::events::BookingCancelled;
+use crate::state::{Booking, BookingStatus, CampaignAccount, BOOKING_SEED, CAMPAIGN_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelBooking<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BOOKING_SEED,
+            campaign_account.key().as_ref(),
+            booking.node_account.as_ref(),
+            &booking.booking_id.to_le_bytes(),
+        ],
+        bump = booking.bump,
+        has_one = campaign_account,
+        has_one = authority @ SamizdatError::Unauthorized,
+        close = authority,
+    )]
+    pub booking: Account<'info, Booking>,
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_cancel_booking(ctx: Context<CancelBooking>) -> Result<()> {
+    // Once accepted, the operator has set the window aside and the booking
+    // runs to settlement
+    let booking = &ctx.accounts.booking;
+    require!(
+        booking.status == BookingStatus::Proposed,
+        SamizdatError::InvalidBookingStatus
+    );
+
+    let released = booking.undelivered_escrow();
+    let campaign = &mut ctx.accounts.campaign_account;
+    campaign.release_budget(released);
+    campaign.open_bookings = campaign
+        .open_bookings
+        .checked_sub(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
+
+    emit_cpi!(BookingCancelled {
+        booking: booking.key(),
+        campaign: booking.campaign_account,
+        node: booking.node_account,
+        released,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
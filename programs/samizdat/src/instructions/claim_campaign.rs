@@ -1,12 +1,20 @@
 use crate::errors::SamizdatError;
+use crate::events::PlayClaimed;
 use crate::state::{
-    CampaignAccount, CampaignStatus, ClaimCooldown, NodeAccount, NodeStatus, PlayRecord,
-    PlayStatus, CAMPAIGN_SEED, COOLDOWN_SEED, NODE_ACCOUNT_SEED, PLAY_RECORD_SEED,
+    ApprovedCampaign, Booking, CampaignAccount, CampaignStatus, ClaimCooldown, FrequencyTracker,
+    NodeAccount, NodeDenylist, NodeStatus, PlayRecord, PlayStatus, ProtocolConfig,
+    PublisherAccount, PublisherStatus, SlotAuction, APPROVED_CAMPAIGN_SEED, BOOKING_SEED,
+    CAMPAIGN_SEED, COOLDOWN_SEED, FREQUENCY_SEED, NODE_ACCOUNT_SEED, NODE_DENYLIST_SEED,
+    PLAY_RECORD_SEED, PROTOCOL_CONFIG_SEED, PUBLISHER_SEED, SLOT_AUCTION_SEED,
 };
+use crate::vault::{self, TokenVault};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(cid_index: u8, claim_nonce: i64)]
+#[instruction(creative_index: u8, claim_nonce: i64)]
 pub struct ClaimCampaign<'info> {
     #[account(
         init,
@@ -37,13 +45,35 @@ pub struct ClaimCampaign<'info> {
     )]
     pub claim_cooldown: Account<'info, ClaimCooldown>,
 
+    /// Tracks the publisher's plays on this node across all its campaigns.
+    /// Created on first claim, updated on subsequent claims.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FrequencyTracker::INIT_SPACE,
+        seeds = [
+            FREQUENCY_SEED,
+            publisher_account.key().as_ref(),
+            node_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub frequency_tracker: Account<'info, FrequencyTracker>,
+
     #[account(
         mut,
         seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
         bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
     )]
     pub campaign_account: Account<'info, CampaignAccount>,
 
+    #[account(
+        seeds = [PUBLISHER_SEED, publisher_account.authority.as_ref()],
+        bump = publisher_account.bump,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
     #[account(
         mut,
         seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
@@ -55,103 +85,175 @@ pub struct ClaimCampaign<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Settled auction for this node's current slot. When present, the campaign
+    /// must be its winner and the play is paid at the clearing price.
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, node_account.key().as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+        has_one = node_account,
+    )]
+    pub slot_auction: Option<Account<'info, SlotAuction>>,
+
+    /// Booking covering the current time. Required while the node is booked,
+    /// and only the booked campaign can be claimed then.
+    #[account(
+        mut,
+        seeds = [
+            BOOKING_SEED,
+            campaign_account.key().as_ref(),
+            node_account.key().as_ref(),
+            &booking.booking_id.to_le_bytes(),
+        ],
+        bump = booking.bump,
+    )]
+    pub booking: Option<Account<'info, Booking>>,
+
+    /// CHECK: The campaign's NodeDenylist PDA. It only exists once the
+    /// publisher creates a denylist, and is only read when owned by this
+    /// program.
+    #[account(
+        seeds = [NODE_DENYLIST_SEED, campaign_account.key().as_ref()],
+        bump,
+    )]
+    pub node_denylist: UncheckedAccount<'info>,
+
+    /// Operator's approval of the claimed creative. Required when the node
+    /// has requires_approval set.
+    #[account(
+        seeds = [
+            APPROVED_CAMPAIGN_SEED,
+            node_account.key().as_ref(),
+            campaign_account.key().as_ref(),
+            &approved_campaign.content_hash,
+        ],
+        bump = approved_campaign.bump,
+    )]
+    pub approved_campaign: Option<Account<'info, ApprovedCampaign>>,
+
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = campaign_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn process_claim_campaign(
     ctx: Context<ClaimCampaign>,
-    cid_index: u8,
+    creative_index: u8,
     claim_nonce: i64,
+    allowlist_proof: Option<Vec<[u8; 32]>>,
 ) -> Result<()> {
     let campaign = &ctx.accounts.campaign_account;
     let node = &ctx.accounts.node_account;
 
+    // A claim under the node's accepted booking is held to the booking's
+    // terms only: pausing, closing, flight, pacing, cooldown, targeting and
+    // list changes the publisher makes after acceptance don't apply to it
+    let clock = Clock::get()?;
+    let booked = match (
+        ctx.accounts.booking.as_ref(),
+        node.booking_at(clock.unix_timestamp),
+    ) {
+        (Some(booking), Some(window)) => window.booking == booking.key(),
+        _ => false,
+    };
+
     // Validate states
     require!(
-        campaign.status == CampaignStatus::Active,
-        SamizdatError::CampaignNotActive
+        !ctx.accounts.protocol_config.paused,
+        SamizdatError::ProtocolPaused
     );
     require!(
-        node.status == NodeStatus::Active,
-        SamizdatError::NodeNotActive
+        booked || campaign.status == CampaignStatus::Active,
+        SamizdatError::CampaignNotActive
     );
     require!(
-        campaign.plays_remaining > 0,
-        SamizdatError::NoPlaysRemaining
+        ctx.accounts.publisher_account.status == PublisherStatus::Active,
+        SamizdatError::PublisherNotActive
     );
-
-    // Validate CID index
     require!(
-        (cid_index as usize) < campaign.cids.len(),
-        SamizdatError::InvalidCidIndex
+        node.status == NodeStatus::Active,
+        SamizdatError::NodeNotActive
     );
+    // Validate flight window
+    if !booked {
+        if let Some(start) = campaign.start_at {
+            require!(
+                clock.unix_timestamp >= start,
+                SamizdatError::CampaignNotStarted
+            );
+        }
+        if let Some(end) = campaign.end_at {
+            require!(clock.unix_timestamp < end, SamizdatError::CampaignEnded);
+        }
+    }
 
-    // Validate content does not violate node's blocked tag policy
+    // Validate creative index and that the node's screen can show it
+    let creative = campaign
+        .creatives
+        .get(creative_index as usize)
+        .ok_or(SamizdatError::InvalidCreativeIndex)?;
     require!(
-        campaign.tag_mask & node.blocked_tag_mask == 0,
-        SamizdatError::ContentFilterViolation
+        creative.fits(&node.resolution),
+        SamizdatError::ResolutionTooLow
     );
-
-    // Validate campaign target filters match this node
-    let filters = &campaign.target_filters;
-    if let Some(min) = filters.min_footfall {
-        require!(
-            node.estimated_footfall >= min,
-            SamizdatError::TargetMismatch
-        );
-    }
-    if let Some(max) = filters.max_footfall {
-        require!(
-            node.estimated_footfall <= max,
-            SamizdatError::TargetMismatch
-        );
-    }
-    if !filters.screen_sizes.is_empty() {
-        require!(
-            filters.screen_sizes.contains(&node.screen_size),
-            SamizdatError::TargetMismatch
-        );
-    }
-    if let Some(bounds) = filters.geo_bounds {
+    // Nodes in approval mode only play creatives their operator approved
+    if node.requires_approval {
+        let approval = ctx
+            .accounts
+            .approved_campaign
+            .as_ref()
+            .ok_or(SamizdatError::CampaignNotApproved)?;
         require!(
-            node.location.latitude >= bounds.min_lat
-                && node.location.latitude <= bounds.max_lat
-                && node.location.longitude >= bounds.min_lon
-                && node.location.longitude <= bounds.max_lon,
-            SamizdatError::TargetMismatch
+            approval.content_hash == creative.content_hash,
+            SamizdatError::CampaignNotApproved
         );
     }
-    if !filters.establishment_types.is_empty() {
-        require!(
-            filters
-                .establishment_types
-                .contains(&node.establishment_type),
-            SamizdatError::TargetMismatch
-        );
-    }
-    if !filters.required_landmarks.is_empty() {
-        for required in &filters.required_landmarks {
-            require!(
-                node.landmarks.contains(required),
-                SamizdatError::TargetMismatch
-            );
-        }
+
+    // Validate content policy and target filters match this node
+    if booked {
+        campaign.check_content_policy(node)?;
+    } else {
+        campaign.check_node_eligibility(node, clock.unix_timestamp)?;
+        let denylist = NodeDenylist::load(&ctx.accounts.node_denylist)?;
+        campaign.check_node_lists(
+            &node.key(),
+            node,
+            allowlist_proof.as_deref(),
+            denylist.as_ref(),
+        )?;
     }
 
-    // Validate vault has enough funds (excess lamports beyond rent-exempt minimum)
-    let rent = Rent::get()?;
-    let campaign_info = ctx.accounts.campaign_account.to_account_info();
-    let rent_exempt_min = rent.minimum_balance(campaign_info.data_len());
-    let vault_balance = campaign_info.lamports().saturating_sub(rent_exempt_min);
-    require!(
-        vault_balance >= campaign.bounty_per_play,
-        SamizdatError::InsufficientFunds
-    );
+    // Vault funds: token balance, or excess lamports beyond rent-exempt
+    // minimum for SOL campaigns
+    let token_vault = TokenVault::resolve(
+        campaign.mint,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    let vault_balance = vault::campaign_balance(
+        &ctx.accounts.campaign_account.to_account_info(),
+        token_vault.as_ref(),
+    )?;
 
     // Enforce per-node cooldown
-    let clock = Clock::get()?;
     let cooldown = &ctx.accounts.claim_cooldown;
-    if cooldown.last_claimed_at > 0 {
+    if !booked && cooldown.last_claimed_at > 0 {
         require!(
             clock.unix_timestamp >= cooldown.last_claimed_at + campaign.claim_cooldown,
             SamizdatError::CooldownNotExpired
@@ -161,17 +263,110 @@ pub fn process_claim_campaign(
     let campaign_key = ctx.accounts.campaign_account.key();
     let node_key = ctx.accounts.node_account.key();
 
-    // Decrement plays_remaining
+    // A booked node only plays the booked campaign, paid from the booking's
+    // escrow, and an auctioned slot only plays through its SlotAuction. The
+    // node keeps bookings and auctioned slots from overlapping. Other claims
+    // reserve their bounty against the unreserved vault balance.
+    let booked_window = node.booking_at(clock.unix_timestamp).copied();
+    let auctioned_slot = node.auctioned_slot_at(clock.unix_timestamp).copied();
+    let booking_price = match (ctx.accounts.booking.as_mut(), booked_window) {
+        (Some(booking), Some(window)) if window.booking == booking.key() => {
+            Some(booking.record_claim(clock.unix_timestamp)?)
+        }
+        (None, None) => None,
+        _ => return err!(SamizdatError::NodeBooked),
+    };
+    match (ctx.accounts.slot_auction.as_ref(), auctioned_slot) {
+        (Some(auction), Some(slot)) if slot.slot_auction == auction.key() => {}
+        (None, None) => {}
+        _ => return err!(SamizdatError::SlotAuctioned),
+    }
+    let booking_key = ctx.accounts.booking.as_ref().map(|b| b.key());
+
+    // Consume the won slot, freeing the node once its winner has played
+    let auction_price = match ctx.accounts.slot_auction.as_mut() {
+        Some(auction) => {
+            let price = auction.claim(campaign_key, clock.unix_timestamp)?;
+            let auction_key = auction.key();
+            ctx.accounts
+                .node_account
+                .remove_auctioned_slot(&auction_key);
+            Some(price)
+        }
+        None => None,
+    };
+    let fixed_price = booking_price.or(auction_price);
+
+    // Track the outstanding claim. Its reservation is kept on the PlayRecord
+    // so a later change to the campaign's ceiling cannot unbalance it.
     let campaign = &mut ctx.accounts.campaign_account;
-    campaign.plays_remaining = campaign.plays_remaining.checked_sub(1).unwrap();
+    let reserved_amount = match booking_price {
+        Some(_) => 0,
+        None => auction_price.unwrap_or(campaign.bounty_per_play),
+    };
+    if booking_price.is_none() {
+        campaign.reserve_play(vault_balance, reserved_amount)?;
+    }
+    campaign.outstanding_claims = campaign
+        .outstanding_claims
+        .checked_add(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
 
-    // Update cooldown tracker
-    ctx.accounts.claim_cooldown.set_inner(ClaimCooldown {
-        campaign: campaign_key,
-        node: node_key,
-        last_claimed_at: clock.unix_timestamp,
-        bump: ctx.bumps.claim_cooldown,
-    });
+    // Enforce daily pacing cap
+    if !booked {
+        campaign.record_daily_play(clock.unix_timestamp)?;
+    }
+
+    ctx.accounts.node_account.record_claim()?;
+
+    // Enforce the node's price floor on what the play will pay
+    let node = &ctx.accounts.node_account;
+    let play_price = match fixed_price {
+        Some(price) => price,
+        None => campaign.play_price(node, &campaign.creatives[creative_index as usize])?,
+    };
+    require!(
+        play_price >= node.min_bounty_per_play,
+        SamizdatError::BountyBelowNodeFloor
+    );
+
+    // Update cooldown tracker and burn the nonce
+    let cooldown = &mut ctx.accounts.claim_cooldown;
+    cooldown.campaign = campaign_key;
+    cooldown.node = node_key;
+    cooldown.last_claimed_at = clock.unix_timestamp;
+    cooldown.bump = ctx.bumps.claim_cooldown;
+    cooldown.record_nonce(claim_nonce)?;
+
+    // Enforce the publisher's cooldown and frequency cap on this node
+    let tracker = &mut ctx.accounts.frequency_tracker;
+    tracker.publisher_account = ctx.accounts.publisher_account.key();
+    tracker.node_account = node_key;
+    tracker.bump = ctx.bumps.frequency_tracker;
+    if !booked {
+        tracker.record_claim(&ctx.accounts.publisher_account, clock.unix_timestamp)?;
+    }
+
+    // Escrow the claim bond in the PlayRecord itself
+    let claim_bond = ctx.accounts.protocol_config.claim_bond;
+    if claim_bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.play_record.to_account_info(),
+                },
+            ),
+            claim_bond,
+        )?;
+    }
+
+    // Fix the confirmation deadline so later timeout changes don't apply
+    let expires_at = clock
+        .unix_timestamp
+        .checked_add(ctx.accounts.protocol_config.play_timeout)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
 
     // Initialize PlayRecord
     ctx.accounts.play_record.set_inner(PlayRecord {
@@ -179,12 +374,32 @@ pub fn process_claim_campaign(
         node_account: node_key,
         nonce: claim_nonce,
         claimed_at: clock.unix_timestamp,
+        expires_at,
         confirmed_at: 0,
-        cid_index,
+        creative_index,
         payment_amount: 0,
+        fee_amount: 0,
+        duration_ms: 0,
+        fixed_price,
+        footfall: ctx.accounts.node_account.estimated_footfall,
+        booking: booking_key,
+        bond_amount: claim_bond,
+        reserved_amount,
         status: PlayStatus::Claimed,
         bump: ctx.bumps.play_record,
     });
 
+    emit_cpi!(PlayClaimed {
+        play_record: ctx.accounts.play_record.key(),
+        campaign: campaign_key,
+        node: node_key,
+        nonce: claim_nonce,
+        creative_index,
+        bond_amount: claim_bond,
+        fixed_price,
+        booking: booking_key,
+        claimed_at: clock.unix_timestamp,
+    });
+
     Ok(())
 }
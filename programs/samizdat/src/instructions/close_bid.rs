@@ -0,0 +1,71 @@
+use crate::errors::SamizdatError;
+use crate::events::BidClosed;
+use crate::state::{AuctionStatus, Bid, SlotAuction, BID_SEED, SLOT_AUCTION_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseBid<'info> {
+    /// The campaign may already be closed, so the bid is located via its
+    /// stored campaign key rather than a campaign account.
+    #[account(
+        mut,
+        seeds = [BID_SEED, slot_auction.key().as_ref(), bid.campaign_account.as_ref()],
+        bump = bid.bump,
+        has_one = slot_auction,
+        has_one = authority @ SamizdatError::Unauthorized,
+        close = authority,
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, slot_auction.node_account.as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_close_bid(ctx: Context<CloseBid>) -> Result<()> {
+    // Bids are part of the auction's record until it settles
+    require!(
+        ctx.accounts.slot_auction.status != AuctionStatus::Open,
+        SamizdatError::BiddingNotClosed
+    );
+
+    // A bid that was never revealed forfeits part of its deposit to the
+    // auction, which the operator collects on close_slot_auction. The rest
+    // leaves with the rent.
+    let forfeited = ctx.accounts.bid.forfeited_deposit();
+    if forfeited > 0 {
+        **ctx
+            .accounts
+            .bid
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= forfeited;
+        **ctx
+            .accounts
+            .slot_auction
+            .to_account_info()
+            .try_borrow_mut_lamports()? += forfeited;
+    }
+
+    let auction = &mut ctx.accounts.slot_auction;
+    auction.open_bids = auction
+        .open_bids
+        .checked_sub(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
+
+    emit_cpi!(BidClosed {
+        bid: ctx.accounts.bid.key(),
+        slot_auction: auction.key(),
+        campaign: ctx.accounts.bid.campaign_account,
+        deposit_forfeited: forfeited,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,14 @@
+use crate::discovery;
 use crate::errors::SamizdatError;
-use crate::state::{CampaignAccount, PublisherAccount, CAMPAIGN_SEED, PUBLISHER_SEED};
+use crate::events::{CampaignClosed, CampaignDraining};
+use crate::state::{
+    CampaignAccount, CampaignStatus, PublisherAccount, CAMPAIGN_SEED, PUBLISHER_SEED,
+};
+use crate::vault::{self, TokenVault};
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CloseCampaign<'info> {
     #[account(
@@ -9,7 +16,6 @@ pub struct CloseCampaign<'info> {
         seeds = [CAMPAIGN_SEED, publisher_account.key().as_ref(), &campaign_account.campaign_id.to_le_bytes()],
         bump = campaign_account.bump,
         has_one = publisher_account @ SamizdatError::PublisherMismatch,
-        close = authority,
     )]
     pub campaign_account: Account<'info, CampaignAccount>,
 
@@ -22,10 +28,89 @@ pub struct CloseCampaign<'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Publisher token account receiving the unspent SPL budget.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-pub fn process_close_campaign(_ctx: Context<CloseCampaign>) -> Result<()> {
-    // MVP: No unclaimed PlayRecord check - just closes and returns funds
-    // The `close = authority` constraint handles lamport transfer and account zeroing
-    Ok(())
+pub fn process_close_campaign<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseCampaign<'info>>,
+) -> Result<()> {
+    // Phase 1: stop new claims. Outstanding claims can still be
+    // confirmed or timed out, and open bookings settled or cancelled,
+    // against the remaining vault.
+    let campaign = &mut ctx.accounts.campaign_account;
+    campaign.status = CampaignStatus::Draining;
+    // Drop out of discovery on the first call, so a draining campaign's
+    // final close needs no GeoIndex accounts
+    discovery::update_geo_indexes(
+        campaign.key(),
+        &campaign.geo_cells,
+        &[],
+        ctx.remaining_accounts,
+        &ctx.accounts.authority.to_account_info(),
+        None,
+    )?;
+    campaign.geo_cells.clear();
+    let clock = Clock::get()?;
+    if campaign.outstanding_claims > 0 || campaign.open_bookings > 0 {
+        emit_cpi!(CampaignDraining {
+            campaign: campaign.key(),
+            outstanding_claims: campaign.outstanding_claims,
+            open_bookings: campaign.open_bookings,
+            timestamp: clock.unix_timestamp,
+        });
+        return Ok(());
+    }
+
+    // Phase 2: nothing left to settle, refund the vault and close.
+    // SPL campaigns refund and close the token vault first; closing the
+    // campaign account returns its lamports (SOL vault + rent) to the publisher.
+    let token_vault = TokenVault::resolve(
+        ctx.accounts.campaign_account.mint,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    let refunded = vault::campaign_balance(
+        &ctx.accounts.campaign_account.to_account_info(),
+        token_vault.as_ref(),
+    )?;
+    if let Some(token_vault) = token_vault {
+        vault::drain_token_vault(
+            &ctx.accounts.campaign_account,
+            &token_vault,
+            ctx.accounts.authority_token_account.as_ref(),
+            &ctx.accounts.authority.to_account_info(),
+        )?;
+    }
+
+    emit_cpi!(CampaignClosed {
+        campaign: ctx.accounts.campaign_account.key(),
+        publisher: ctx.accounts.publisher_account.key(),
+        refunded,
+        timestamp: clock.unix_timestamp,
+    });
+
+    ctx.accounts
+        .campaign_account
+        .close(ctx.accounts.authority.to_account_info())
 }
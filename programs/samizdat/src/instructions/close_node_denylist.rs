@@ -0,0 +1,33 @@
+use crate::errors::SamizdatError;
+use crate::events::NodeDenylistClosed;
+use crate::state::{NodeDenylist, NODE_DENYLIST_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseNodeDenylist<'info> {
+    /// The campaign may already be closed, so the denylist is located via
+    /// its stored campaign key rather than a campaign account.
+    #[account(
+        mut,
+        seeds = [NODE_DENYLIST_SEED, node_denylist.campaign_account.as_ref()],
+        bump = node_denylist.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+        close = authority,
+    )]
+    pub node_denylist: Account<'info, NodeDenylist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_close_node_denylist(ctx: Context<CloseNodeDenylist>) -> Result<()> {
+    // Closing lifts every entry; the rent goes back to the publisher
+    emit_cpi!(NodeDenylistClosed {
+        node_denylist: ctx.accounts.node_denylist.key(),
+        campaign: ctx.accounts.node_denylist.campaign_account,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
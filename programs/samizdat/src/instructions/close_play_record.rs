@@ -0,0 +1,56 @@
+use crate::errors::SamizdatError;
+use crate::events::PlayRecordClosed;
+use crate::state::{NodeAccount, PlayRecord, PlayStatus, NODE_ACCOUNT_SEED, PLAY_RECORD_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePlayRecord<'info> {
+    /// The campaign may already be closed, so the record is located via its
+    /// stored campaign key rather than a campaign account.
+    #[account(
+        mut,
+        seeds = [
+            PLAY_RECORD_SEED,
+            play_record.campaign_account.as_ref(),
+            node_account.key().as_ref(),
+            &play_record.nonce.to_le_bytes(),
+        ],
+        bump = play_record.bump,
+        has_one = node_account,
+        close = authority,
+    )]
+    pub play_record: Account<'info, PlayRecord>,
+
+    #[account(
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_close_play_record(ctx: Context<ClosePlayRecord>) -> Result<()> {
+    // Claimed records still hold an outstanding claim on the campaign;
+    // timed-out ones were already closed by timeout_play. Replay protection
+    // for the nonce lives on ClaimCooldown, so the PDA cannot be recreated
+    // once closed.
+    let play_record = &ctx.accounts.play_record;
+    require!(
+        play_record.status == PlayStatus::Paid,
+        SamizdatError::PlayNotSettled
+    );
+
+    emit_cpi!(PlayRecordClosed {
+        play_record: play_record.key(),
+        campaign: play_record.campaign_account,
+        node: play_record.node_account,
+        status: play_record.status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::errors::SamizdatError;
+use crate::events::SlotAuctionClosed;
+use crate::state::{AuctionStatus, NodeAccount, SlotAuction, NODE_ACCOUNT_SEED, SLOT_AUCTION_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseSlotAuction<'info> {
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, node_account.key().as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+        has_one = node_account,
+        close = authority,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_close_slot_auction(ctx: Context<CloseSlotAuction>) -> Result<()> {
+    // Every bid must be refunded first, and a won slot stays open until it is
+    // claimed, released or its window has passed
+    let auction = &ctx.accounts.slot_auction;
+    let clock = Clock::get()?;
+    require!(
+        auction.open_bids == 0
+            && (matches!(
+                auction.status,
+                AuctionStatus::Claimed | AuctionStatus::Released
+            ) || clock.unix_timestamp >= auction.slot_end),
+        SamizdatError::AuctionNotFinished
+    );
+
+    ctx.accounts
+        .node_account
+        .remove_auctioned_slot(&auction.key());
+
+    emit_cpi!(SlotAuctionClosed {
+        slot_auction: auction.key(),
+        node: auction.node_account,
+        status: auction.status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use crate::errors::SamizdatError;
+use crate::events::BidCommitted;
+use crate::state::{
+    ApprovedCampaign, AuctionStatus, Bid, CampaignAccount, CampaignStatus, NodeAccount,
+    NodeDenylist, ProtocolConfig, PublisherAccount, PublisherStatus, SlotAuction,
+    APPROVED_CAMPAIGN_SEED, BID_SEED, CAMPAIGN_SEED, NODE_ACCOUNT_SEED, NODE_DENYLIST_SEED,
+    PROTOCOL_CONFIG_SEED, PUBLISHER_SEED, SLOT_AUCTION_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    /// One bid per campaign per auction.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [BID_SEED, slot_auction.key().as_ref(), campaign_account.key().as_ref()],
+        bump,
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, node_account.key().as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+        has_one = node_account,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(
+        seeds = [CAMPAIGN_SEED, publisher_account.key().as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(
+        seeds = [PUBLISHER_SEED, authority.key().as_ref()],
+        bump = publisher_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: The campaign's NodeDenylist PDA. It only exists once the
+    /// publisher creates a denylist, and is only read when owned by this
+    /// program.
+    #[account(
+        seeds = [NODE_DENYLIST_SEED, campaign_account.key().as_ref()],
+        bump,
+    )]
+    pub node_denylist: UncheckedAccount<'info>,
+
+    /// Operator's approval of one of the campaign's creatives. Required when
+    /// the node has requires_approval set.
+    #[account(
+        seeds = [
+            APPROVED_CAMPAIGN_SEED,
+            node_account.key().as_ref(),
+            campaign_account.key().as_ref(),
+            &approved_campaign.content_hash,
+        ],
+        bump = approved_campaign.bump,
+    )]
+    pub approved_campaign: Option<Account<'info, ApprovedCampaign>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_commit_bid(
+    ctx: Context<CommitBid>,
+    commitment: [u8; 32],
+    allowlist_proof: Option<Vec<[u8; 32]>>,
+) -> Result<()> {
+    let auction = &ctx.accounts.slot_auction;
+    let campaign = &ctx.accounts.campaign_account;
+
+    // Validate states
+    require!(
+        !ctx.accounts.protocol_config.paused,
+        SamizdatError::ProtocolPaused
+    );
+    require!(
+        auction.status == AuctionStatus::Open,
+        SamizdatError::AuctionNotOpen
+    );
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < auction.commit_ends_at,
+        SamizdatError::BiddingClosed
+    );
+    require!(
+        campaign.status == CampaignStatus::Active,
+        SamizdatError::CampaignNotActive
+    );
+    require!(
+        ctx.accounts.publisher_account.status == PublisherStatus::Active,
+        SamizdatError::PublisherNotActive
+    );
+    if let Some(end) = campaign.end_at {
+        require!(auction.slot_start < end, SamizdatError::CampaignEnded);
+    }
+
+    // Only campaigns that could claim this node at the start of the slot may
+    // bid: eligible, allowed by the campaign's lists and, in approval mode,
+    // with a creative the operator approved
+    let node = &ctx.accounts.node_account;
+    campaign.check_node_eligibility(node, auction.slot_start)?;
+    let denylist = NodeDenylist::load(&ctx.accounts.node_denylist)?;
+    campaign.check_node_lists(
+        &node.key(),
+        node,
+        allowlist_proof.as_deref(),
+        denylist.as_ref(),
+    )?;
+    if node.requires_approval {
+        let approval = ctx
+            .accounts
+            .approved_campaign
+            .as_ref()
+            .ok_or(SamizdatError::CampaignNotApproved)?;
+        require!(
+            campaign
+                .creatives
+                .iter()
+                .any(|creative| creative.content_hash == approval.content_hash),
+            SamizdatError::CampaignNotApproved
+        );
+    }
+
+    // Escrow the deposit in the Bid itself
+    let deposit = auction.bid_deposit;
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.bid.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+
+    let auction_key = auction.key();
+    let campaign_key = campaign.key();
+    ctx.accounts.slot_auction.record_commit()?;
+
+    ctx.accounts.bid.set_inner(Bid {
+        slot_auction: auction_key,
+        campaign_account: campaign_key,
+        authority: ctx.accounts.authority.key(),
+        commitment,
+        deposit,
+        amount: 0,
+        revealed: false,
+        committed_at: clock.unix_timestamp,
+        revealed_at: 0,
+        bump: ctx.bumps.bid,
+    });
+
+    emit_cpi!(BidCommitted {
+        bid: ctx.accounts.bid.key(),
+        slot_auction: auction_key,
+        campaign: campaign_key,
+        deposit,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
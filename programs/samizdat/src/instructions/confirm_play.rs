@@ -1,10 +1,16 @@
+use crate::definitions::{ProofPayloadV1, ProofPayloadV2};
 use crate::errors::SamizdatError;
+use crate::events::PlayConfirmed;
 use crate::state::{
-    CampaignAccount, NodeAccount, PlayRecord, PlayStatus, PublisherAccount, CAMPAIGN_SEED,
-    NODE_ACCOUNT_SEED, PLAY_RECORD_SEED, PLAY_TIMEOUT_SECONDS, PUBLISHER_SEED,
+    Booking, CampaignAccount, NodeAccount, PlayRecord, PlayStatus, PricingModel, ProtocolConfig,
+    PublisherAccount, PublisherStatus, BOOKING_SEED, CAMPAIGN_SEED, NODE_ACCOUNT_SEED,
+    PLAY_RECORD_SEED, PROTOCOL_CONFIG_SEED, PUBLISHER_SEED,
 };
+use crate::vault::{self, TokenVault};
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ConfirmPlay<'info> {
     #[account(
@@ -46,9 +52,73 @@ pub struct ConfirmPlay<'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Protocol fee recipient, validated against protocol_config.treasury
+    #[account(
+        mut,
+        address = protocol_config.treasury @ SamizdatError::TreasuryMismatch,
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, read to locate the Ed25519 proof instruction
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Booking the play was claimed under; required for booked plays.
+    #[account(
+        mut,
+        seeds = [
+            BOOKING_SEED,
+            campaign_account.key().as_ref(),
+            node_account.key().as_ref(),
+            &booking.booking_id.to_le_bytes(),
+        ],
+        bump = booking.bump,
+    )]
+    pub booking: Option<Account<'info, Booking>>,
+
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Operator token account receiving SPL bounties.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury token account receiving the protocol fee on SPL bounties.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-pub fn process_confirm_play(ctx: Context<ConfirmPlay>) -> Result<()> {
+pub fn process_confirm_play(
+    ctx: Context<ConfirmPlay>,
+    proof_timestamp: i64,
+    duration_ms: Option<u32>,
+) -> Result<()> {
     let play_record = &ctx.accounts.play_record;
 
     // Validate play status
@@ -57,51 +127,195 @@ pub fn process_confirm_play(ctx: Context<ConfirmPlay>) -> Result<()> {
         SamizdatError::InvalidPlayStatus
     );
 
+    // A suspended publisher's campaigns pay nothing out, including plays
+    // claimed before the suspension; timeout_play refunds the bond for those
+    require!(
+        ctx.accounts.publisher_account.status == PublisherStatus::Active,
+        SamizdatError::PublisherNotActive
+    );
+
     // Validate within timeout window
     let clock = Clock::get()?;
     require!(
-        clock.unix_timestamp <= play_record.claimed_at + PLAY_TIMEOUT_SECONDS,
+        clock.unix_timestamp <= play_record.expires_at,
         SamizdatError::TimeoutExpired
     );
 
-    let bounty = ctx.accounts.campaign_account.bounty_per_play;
+    // Validate the renderer signed a proof for this exact play
+    require!(
+        proof_timestamp >= play_record.claimed_at && proof_timestamp <= clock.unix_timestamp,
+        SamizdatError::InvalidProofTimestamp
+    );
+    let campaign = &ctx.accounts.campaign_account;
+    let instructions_sysvar = ctx.accounts.instructions_sysvar.to_account_info();
+    let signing_key = &ctx.accounts.node_account.signing_key;
+
+    let verify_v1 = || {
+        require!(duration_ms.is_none(), SamizdatError::InvalidDuration);
+        ProofPayloadV1 {
+            campaign: campaign.key(),
+            node: ctx.accounts.node_account.key(),
+            nonce: play_record.nonce,
+            timestamp: proof_timestamp,
+        }
+        .verify(&instructions_sysvar, signing_key)
+    };
+
+    // Price the play: the auction clearing price or booking price when fixed
+    // at claim, otherwise a flat bounty, by the node's footfall when claimed,
+    // or pro-rata for the proven display time
+    let bounty = match (play_record.fixed_price, campaign.pricing) {
+        (Some(clearing_price), _) => {
+            verify_v1()?;
+            clearing_price
+        }
+        (None, PricingModel::PerPlay) => {
+            verify_v1()?;
+            campaign.bounty_per_play
+        }
+        (None, PricingModel::Cpm { cpm_rate }) => {
+            verify_v1()?;
+            // Capped at the ceiling reserved at claim time
+            PricingModel::cpm_payout(cpm_rate, play_record.footfall, play_record.reserved_amount)
+        }
+        (
+            None,
+            PricingModel::PerSecond {
+                price_per_second,
+                max_duration_ms,
+            },
+        ) => {
+            let duration_ms = duration_ms.ok_or(SamizdatError::InvalidDuration)?;
+            let creative = &campaign.creatives[play_record.creative_index as usize];
+            require!(
+                duration_ms > 0
+                    && duration_ms <= creative.duration_ms
+                    && duration_ms <= max_duration_ms,
+                SamizdatError::InvalidDuration
+            );
+            ProofPayloadV2 {
+                campaign: campaign.key(),
+                node: ctx.accounts.node_account.key(),
+                nonce: play_record.nonce,
+                timestamp: proof_timestamp,
+                duration_ms,
+            }
+            .verify(&instructions_sysvar, signing_key)?;
+            // The claim only checked the floor against a full play
+            let payout = PricingModel::per_second_payout(price_per_second, duration_ms)?;
+            require!(
+                payout >= ctx.accounts.node_account.min_bounty_per_play,
+                SamizdatError::BountyBelowNodeFloor
+            );
+            payout
+        }
+    };
+
+    // Split bounty between operator and protocol treasury
+    let mut fee = ctx.accounts.protocol_config.fee_for(bounty);
+    let payout = bounty - fee;
+
+    // Transfer shares from campaign vault. A fee the treasury cannot receive
+    // stays in the campaign rather than blocking the operator's payment.
+    let token_vault = TokenVault::resolve(
+        ctx.accounts.campaign_account.mint,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    if fee > 0
+        && !vault::can_receive(
+            token_vault.as_ref(),
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_token_account.as_ref(),
+            fee,
+        )?
+    {
+        fee = 0;
+    }
+    vault::pay_out(
+        &ctx.accounts.campaign_account,
+        token_vault.as_ref(),
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.authority_token_account.as_ref(),
+        payout,
+    )?;
+    if fee > 0 {
+        vault::pay_out(
+            &ctx.accounts.campaign_account,
+            token_vault.as_ref(),
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.treasury_token_account.as_ref(),
+            fee,
+        )?;
+    }
 
-    // Transfer lamports from campaign PDA to operator wallet
-    let campaign_info = ctx.accounts.campaign_account.to_account_info();
-    let authority_info = ctx.accounts.authority.to_account_info();
-    **campaign_info.try_borrow_mut_lamports()? -= bounty;
-    **authority_info.try_borrow_mut_lamports()? += bounty;
+    // Return the claim bond to the operator
+    let bond = ctx.accounts.play_record.bond_amount;
+    if bond > 0 {
+        **ctx
+            .accounts
+            .play_record
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= bond;
+        **ctx.accounts.authority.try_borrow_mut_lamports()? += bond;
+    }
 
     // Update PlayRecord
     let play_record = &mut ctx.accounts.play_record;
     play_record.status = PlayStatus::Paid;
     play_record.confirmed_at = clock.unix_timestamp;
-    play_record.payment_amount = bounty;
+    play_record.payment_amount = payout;
+    play_record.fee_amount = fee;
+    play_record.duration_ms = duration_ms.unwrap_or(0);
 
-    // Update CampaignAccount
+    // Update CampaignAccount, releasing the claim's reservation or, for a
+    // booked play, its share of the booking escrow
     let campaign = &mut ctx.accounts.campaign_account;
+    match ctx.accounts.play_record.booking {
+        Some(booking_key) => {
+            let booking = ctx
+                .accounts
+                .booking
+                .as_mut()
+                .filter(|booking| booking.key() == booking_key)
+                .ok_or(SamizdatError::BookingMismatch)?;
+            booking.record_delivery()?;
+            campaign.release_budget(bounty);
+        }
+        None => campaign.settle_play(ctx.accounts.play_record.reserved_amount),
+    }
     campaign.plays_completed = campaign
         .plays_completed
         .checked_add(1)
         .ok_or(SamizdatError::ArithmeticOverflow)?;
+    campaign.outstanding_claims = campaign
+        .outstanding_claims
+        .checked_sub(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
 
     // Update NodeAccount
     let node = &mut ctx.accounts.node_account;
-    node.total_plays = node
-        .total_plays
-        .checked_add(1)
-        .ok_or(SamizdatError::ArithmeticOverflow)?;
-    node.total_earnings = node
-        .total_earnings
-        .checked_add(bounty)
-        .ok_or(SamizdatError::ArithmeticOverflow)?;
+    node.record_confirmed_play(payout)?;
 
     // Update PublisherAccount
     let publisher = &mut ctx.accounts.publisher_account;
     publisher.total_spent = publisher
         .total_spent
-        .checked_add(bounty)
+        .checked_add(payout + fee)
         .ok_or(SamizdatError::ArithmeticOverflow)?;
 
+    emit_cpi!(PlayConfirmed {
+        play_record: ctx.accounts.play_record.key(),
+        campaign: ctx.accounts.campaign_account.key(),
+        node: ctx.accounts.node_account.key(),
+        operator: ctx.accounts.authority.key(),
+        payment_amount: payout,
+        fee_amount: fee,
+        duration_ms: duration_ms.unwrap_or(0),
+        proof_timestamp,
+        confirmed_at: clock.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,162 @@
+use crate::errors::SamizdatError;
+use crate::events::BookingCreated;
+use crate::state::{
+    Booking, BookingStatus, CampaignAccount, CampaignStatus, NodeAccount, ProtocolConfig,
+    PublisherAccount, PublisherStatus, BOOKING_SEED, CAMPAIGN_SEED, NODE_ACCOUNT_SEED,
+    PROTOCOL_CONFIG_SEED, PUBLISHER_SEED,
+};
+use crate::vault::{self, TokenVault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(booking_id: u64)]
+pub struct CreateBooking<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Booking::INIT_SPACE,
+        seeds = [
+            BOOKING_SEED,
+            campaign_account.key().as_ref(),
+            node_account.key().as_ref(),
+            &booking_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub booking: Account<'info, Booking>,
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, publisher_account.key().as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(
+        seeds = [PUBLISHER_SEED, authority.key().as_ref()],
+        bump = publisher_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    #[account(
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = campaign_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_create_booking(
+    ctx: Context<CreateBooking>,
+    booking_id: u64,
+    start_at: i64,
+    end_at: i64,
+    guaranteed_plays: u64,
+    price_per_play: u64,
+) -> Result<()> {
+    let campaign = &ctx.accounts.campaign_account;
+
+    // Validate states
+    require!(
+        !ctx.accounts.protocol_config.paused,
+        SamizdatError::ProtocolPaused
+    );
+    require!(
+        campaign.status == CampaignStatus::Active,
+        SamizdatError::CampaignNotActive
+    );
+    require!(
+        ctx.accounts.publisher_account.status == PublisherStatus::Active,
+        SamizdatError::PublisherNotActive
+    );
+
+    // Validate the window and terms
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < start_at
+            && start_at < end_at
+            && guaranteed_plays > 0
+            && price_per_play > 0,
+        SamizdatError::InvalidBooking
+    );
+    if let Some(end) = campaign.end_at {
+        require!(end_at <= end, SamizdatError::InvalidBooking);
+    }
+
+    // Escrow the whole booking out of the unreserved vault balance
+    let escrow = Booking::escrow(guaranteed_plays, price_per_play)?;
+    let token_vault = TokenVault::resolve(
+        campaign.mint,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    let vault_balance = vault::campaign_balance(
+        &ctx.accounts.campaign_account.to_account_info(),
+        token_vault.as_ref(),
+    )?;
+    let campaign = &mut ctx.accounts.campaign_account;
+    campaign.reserve_budget(vault_balance, escrow)?;
+    campaign.open_bookings = campaign
+        .open_bookings
+        .checked_add(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
+
+    let campaign_key = campaign.key();
+    let node_key = ctx.accounts.node_account.key();
+    ctx.accounts.booking.set_inner(Booking {
+        campaign_account: campaign_key,
+        node_account: node_key,
+        authority: ctx.accounts.authority.key(),
+        booking_id,
+        start_at,
+        end_at,
+        guaranteed_plays,
+        price_per_play,
+        plays_claimed: 0,
+        plays_delivered: 0,
+        status: BookingStatus::Proposed,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.booking,
+    });
+
+    emit_cpi!(BookingCreated {
+        booking: ctx.accounts.booking.key(),
+        campaign: campaign_key,
+        node: node_key,
+        booking_id,
+        start_at,
+        end_at,
+        guaranteed_plays,
+        price_per_play,
+        escrowed: escrow,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,11 +1,17 @@
+use crate::discovery;
 use crate::errors::SamizdatError;
+use crate::events::CampaignCreated;
 use crate::state::{
-    CampaignAccount, CampaignStatus, PublisherAccount, PublisherStatus, TargetFilters,
-    CAMPAIGN_SEED, MAX_CIDS, MAX_CID_LENGTH, PUBLISHER_SEED,
+    geo_cells, CampaignAccount, CampaignStatus, Creative, PricingModel, ProtocolConfig,
+    PublisherAccount, PublisherStatus, TargetFilters, CAMPAIGN_SEED, MAX_CREATIVES,
+    PROTOCOL_CONFIG_SEED, PUBLISHER_SEED,
 };
+use crate::vault::{self, TokenVault};
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(campaign_id: u64)]
 pub struct CreateCampaign<'info> {
@@ -29,69 +35,144 @@ pub struct CreateCampaign<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Payment mint for SPL campaigns; omit to pay bounties in SOL.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Campaign-owned token vault (ATA of the campaign PDA).
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = campaign_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn process_create_campaign(
-    ctx: Context<CreateCampaign>,
+pub fn process_create_campaign<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateCampaign<'info>>,
     campaign_id: u64,
-    cids: Vec<String>,
+    creatives: Vec<Creative>,
     bounty_per_play: u64,
+    pricing: PricingModel,
     total_plays: u64,
     tag_mask: u64,
     target_filters: TargetFilters,
     claim_cooldown: i64,
+    start_at: Option<i64>,
+    end_at: Option<i64>,
+    daily_play_cap: Option<u64>,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.protocol_config.paused,
+        SamizdatError::ProtocolPaused
+    );
     let publisher = &ctx.accounts.publisher_account;
     require!(
         publisher.status == PublisherStatus::Active,
         SamizdatError::PublisherNotActive
     );
     require!(
-        !cids.is_empty() && cids.len() <= MAX_CIDS,
-        SamizdatError::TooManyCids
+        !creatives.is_empty() && creatives.len() <= MAX_CREATIVES,
+        SamizdatError::TooManyCreatives
     );
-    for cid in &cids {
-        require!(
-            !cid.is_empty() && cid.len() <= MAX_CID_LENGTH,
-            SamizdatError::InvalidCid
-        );
+    for creative in &creatives {
+        creative.validate()?;
     }
+    Creative::require_distinct_hashes(&creatives)?;
     require!(bounty_per_play > 0, SamizdatError::InvalidBounty);
+    pricing.validate(bounty_per_play)?;
     require!(total_plays > 0, SamizdatError::InvalidPlays);
+    require!(daily_play_cap != Some(0), SamizdatError::InvalidPlays);
     require!(claim_cooldown >= 0, SamizdatError::InvalidAmount);
+    target_filters.validate()?;
+    let geo_cells = geo_cells(&target_filters.geo_regions);
+
+    let clock = Clock::get()?;
+    CampaignAccount::validate_flight_window(start_at, end_at)?;
+    if let Some(end) = end_at {
+        require!(
+            end > clock.unix_timestamp,
+            SamizdatError::InvalidFlightWindow
+        );
+    }
 
     // Publisher must fund the full budget at creation
     let required_funding = bounty_per_play
         .checked_mul(total_plays)
         .ok_or(SamizdatError::ArithmeticOverflow)?;
 
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.authority.to_account_info(),
-                to: ctx.accounts.campaign_account.to_account_info(),
-            },
-        ),
+    let mint = ctx.accounts.mint.as_ref().map(|m| m.key());
+    let token_vault = TokenVault::resolve(
+        mint,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    vault::deposit(
+        &ctx.accounts.campaign_account.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.authority_token_account.as_ref(),
+        token_vault.as_ref(),
+        &ctx.accounts.system_program.to_account_info(),
         required_funding,
     )?;
 
-    let clock = Clock::get()?;
+    // List the campaign in the GeoIndex of every cell its regions touch
+    let geo_cells = discovery::update_geo_indexes(
+        ctx.accounts.campaign_account.key(),
+        &[],
+        &geo_cells,
+        ctx.remaining_accounts,
+        &ctx.accounts.authority.to_account_info(),
+        Some(&ctx.accounts.system_program.to_account_info()),
+    )?;
+
     let publisher_key = ctx.accounts.publisher_account.key();
 
     ctx.accounts.campaign_account.set_inner(CampaignAccount {
         publisher_account: publisher_key,
         campaign_id,
-        cids,
+        mint,
+        creatives,
         bounty_per_play,
+        pricing,
         plays_remaining: total_plays,
         plays_completed: 0,
+        outstanding_claims: 0,
+        reserved_budget: 0,
+        open_bookings: 0,
         tag_mask,
         target_filters,
+        geo_cells,
         status: CampaignStatus::Active,
         claim_cooldown,
+        start_at,
+        end_at,
+        daily_play_cap,
+        pacing_day: 0,
+        plays_today: 0,
         created_at: clock.unix_timestamp,
         bump: ctx.bumps.campaign_account,
     });
@@ -99,5 +180,19 @@ pub fn process_create_campaign(
     let publisher = &mut ctx.accounts.publisher_account;
     publisher.total_campaigns = publisher.total_campaigns.checked_add(1).unwrap();
 
+    emit_cpi!(CampaignCreated {
+        campaign: ctx.accounts.campaign_account.key(),
+        publisher: publisher_key,
+        campaign_id,
+        mint,
+        bounty_per_play,
+        pricing,
+        total_plays,
+        funded_amount: required_funding,
+        start_at,
+        end_at,
+        timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,72 @@
+use crate::discovery;
+use crate::errors::SamizdatError;
+use crate::events::CampaignExpired;
+use crate::state::{
+    CampaignAccount, CampaignStatus, PublisherAccount, CAMPAIGN_SEED, PUBLISHER_SEED,
+};
+use anchor_lang::prelude::*;
+
+/// Permissionless: anyone can expire a campaign once its flight has ended.
+/// The GeoIndex PDAs of its cells are passed as remaining accounts, in
+/// sorted cell order, so the campaign leaves discovery.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, publisher_account.key().as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(
+        seeds = [PUBLISHER_SEED, publisher_account.authority.as_ref()],
+        bump = publisher_account.bump,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    /// CHECK: Publisher wallet, receives the rent of GeoIndexes the campaign
+    /// was the last entry of
+    #[account(mut, address = publisher_account.authority @ SamizdatError::Unauthorized)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn process_expire_campaign<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExpireCampaign<'info>>,
+) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign_account;
+
+    // Only live campaigns can expire; Draining/Closed already wind down
+    require!(
+        matches!(
+            campaign.status,
+            CampaignStatus::Active | CampaignStatus::Paused | CampaignStatus::Depleted
+        ),
+        SamizdatError::InvalidStatusTransition
+    );
+
+    // Validate flight has ended
+    let end = campaign.end_at.ok_or(SamizdatError::FlightNotEnded)?;
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp >= end, SamizdatError::FlightNotEnded);
+
+    campaign.status = CampaignStatus::Expired;
+    discovery::update_geo_indexes(
+        campaign.key(),
+        &campaign.geo_cells,
+        &[],
+        ctx.remaining_accounts,
+        &ctx.accounts.authority.to_account_info(),
+        None,
+    )?;
+    campaign.geo_cells.clear();
+
+    emit_cpi!(CampaignExpired {
+        campaign: campaign.key(),
+        end_at: end,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
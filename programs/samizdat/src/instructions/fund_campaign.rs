@@ -1,10 +1,13 @@
 use crate::errors::SamizdatError;
+use crate::events::CampaignFunded;
 use crate::state::{
     CampaignAccount, CampaignStatus, PublisherAccount, CAMPAIGN_SEED, PUBLISHER_SEED,
 };
+use crate::vault::{self, TokenVault};
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct FundCampaign<'info> {
     #[account(
@@ -25,25 +28,52 @@ pub struct FundCampaign<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn process_fund_campaign(ctx: Context<FundCampaign>, amount: u64) -> Result<()> {
     let campaign = &ctx.accounts.campaign_account;
     require!(
-        campaign.status != CampaignStatus::Closed,
+        !matches!(
+            campaign.status,
+            CampaignStatus::Closed | CampaignStatus::Draining | CampaignStatus::Expired
+        ),
         SamizdatError::CampaignNotActive
     );
     require!(amount > 0, SamizdatError::InvalidAmount);
 
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.authority.to_account_info(),
-                to: ctx.accounts.campaign_account.to_account_info(),
-            },
-        ),
+    let token_vault = TokenVault::resolve(
+        campaign.mint,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    vault::deposit(
+        &ctx.accounts.campaign_account.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.authority_token_account.as_ref(),
+        token_vault.as_ref(),
+        &ctx.accounts.system_program.to_account_info(),
         amount,
     )?;
 
@@ -54,5 +84,12 @@ pub fn process_fund_campaign(ctx: Context<FundCampaign>, amount: u64) -> Result<
         campaign.status = CampaignStatus::Paused;
     }
 
+    emit_cpi!(CampaignFunded {
+        campaign: campaign.key(),
+        amount,
+        status: campaign.status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,68 @@
+use crate::errors::SamizdatError;
+use crate::events::ProtocolConfigUpdated;
+use crate::state::{
+    ProtocolConfig, BPS_DENOMINATOR, DEFAULT_CLAIM_BOND_LAMPORTS, DEFAULT_CRANK_REWARD_BPS,
+    DEFAULT_PLAY_TIMEOUT_SECONDS, PROTOCOL_CONFIG_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Only the program's upgrade authority can bootstrap the config.
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = solana_sdk_ids::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ SamizdatError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_initialize_protocol_config(
+    ctx: Context<InitializeProtocolConfig>,
+    treasury: Pubkey,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(fee_bps <= BPS_DENOMINATOR, SamizdatError::InvalidFee);
+
+    ctx.accounts.protocol_config.set_inner(ProtocolConfig {
+        admin: ctx.accounts.authority.key(),
+        pending_admin: None,
+        treasury,
+        fee_bps,
+        crank_reward_bps: DEFAULT_CRANK_REWARD_BPS,
+        claim_bond: DEFAULT_CLAIM_BOND_LAMPORTS,
+        play_timeout: DEFAULT_PLAY_TIMEOUT_SECONDS,
+        paused: false,
+        bump: ctx.bumps.protocol_config,
+    });
+
+    let config = &ctx.accounts.protocol_config;
+    emit_cpi!(ProtocolConfigUpdated {
+        admin: config.admin,
+        treasury: config.treasury,
+        fee_bps: config.fee_bps,
+        crank_reward_bps: config.crank_reward_bps,
+        claim_bond: config.claim_bond,
+        play_timeout: config.play_timeout,
+        paused: config.paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,23 +1,77 @@
-pub mod add_cids_to_campaign;
+pub mod accept_booking;
+pub mod accept_protocol_admin;
+pub mod add_creatives_to_campaign;
+pub mod approve_campaign;
+pub mod cancel_booking;
 pub mod claim_campaign;
+pub mod close_bid;
 pub mod close_campaign;
+pub mod close_node_denylist;
+pub mod close_play_record;
+pub mod close_slot_auction;
+pub mod commit_bid;
 pub mod confirm_play;
+pub mod create_booking;
 pub mod create_campaign;
+pub mod expire_campaign;
 pub mod fund_campaign;
+pub mod initialize_protocol_config;
+pub mod open_slot_auction;
+pub mod record_lost_dispute;
 pub mod register_node;
 pub mod register_publisher;
+pub mod reinstate_node;
+pub mod reinstate_publisher;
+pub mod release_auctioned_slot;
+pub mod reveal_bid;
+pub mod revoke_campaign_approval;
+pub mod settle_booking;
+pub mod settle_slot_auction;
+pub mod suspend_node;
+pub mod suspend_publisher;
 pub mod timeout_play;
+pub mod transfer_protocol_admin;
 pub mod update_campaign;
+pub mod update_frequency_cap;
+pub mod update_node_denylist;
 pub mod update_node_metadata;
+pub mod update_protocol_config;
 
-pub use add_cids_to_campaign::*;
+pub use accept_booking::*;
+pub use accept_protocol_admin::*;
+pub use add_creatives_to_campaign::*;
+pub use approve_campaign::*;
+pub use cancel_booking::*;
 pub use claim_campaign::*;
+pub use close_bid::*;
 pub use close_campaign::*;
+pub use close_node_denylist::*;
+pub use close_play_record::*;
+pub use close_slot_auction::*;
+pub use commit_bid::*;
 pub use confirm_play::*;
+pub use create_booking::*;
 pub use create_campaign::*;
+pub use expire_campaign::*;
 pub use fund_campaign::*;
+pub use initialize_protocol_config::*;
+pub use open_slot_auction::*;
+pub use record_lost_dispute::*;
 pub use register_node::*;
 pub use register_publisher::*;
+pub use reinstate_node::*;
+pub use reinstate_publisher::*;
+pub use release_auctioned_slot::*;
+pub use reveal_bid::*;
+pub use revoke_campaign_approval::*;
+pub use settle_booking::*;
+pub use settle_slot_auction::*;
+pub use suspend_node::*;
+pub use suspend_publisher::*;
 pub use timeout_play::*;
+pub use transfer_protocol_admin::*;
 pub use update_campaign::*;
+pub use update_frequency_cap::*;
+pub use update_node_denylist::*;
 pub use update_node_metadata::*;
+pub use update_protocol_config::*;
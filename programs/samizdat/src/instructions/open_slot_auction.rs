@@ -0,0 +1,109 @@
+use crate::errors::SamizdatError;
+use crate::events::SlotAuctionOpened;
+use crate::state::{
+    AuctionStatus, AuctionedSlot, NodeAccount, NodeStatus, SlotAuction, NODE_ACCOUNT_SEED,
+    SLOT_AUCTION_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(slot_id: u64)]
+pub struct OpenSlotAuction<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SlotAuction::INIT_SPACE,
+        seeds = [SLOT_AUCTION_SEED, node_account.key().as_ref(), &slot_id.to_le_bytes()],
+        bump,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_open_slot_auction(
+    ctx: Context<OpenSlotAuction>,
+    slot_id: u64,
+    commit_ends_at: i64,
+    reveal_ends_at: i64,
+    slot_start: i64,
+    slot_end: i64,
+    reserve_price: u64,
+    bid_deposit: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.node_account.status == NodeStatus::Active,
+        SamizdatError::NodeNotActive
+    );
+
+    // Commits, then reveals, must both close before the slot they sell begins
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < commit_ends_at
+            && commit_ends_at < reveal_ends_at
+            && reveal_ends_at <= slot_start
+            && slot_start < slot_end,
+        SamizdatError::InvalidAuctionWindow
+    );
+
+    // The slot is held on the node so claims inside it must go through the auction
+    let node_key = ctx.accounts.node_account.key();
+    let auction_key = ctx.accounts.slot_auction.key();
+    ctx.accounts.node_account.add_auctioned_slot(
+        AuctionedSlot {
+            slot_auction: auction_key,
+            slot_start,
+            slot_end,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    ctx.accounts.slot_auction.set_inner(SlotAuction {
+        node_account: node_key,
+        slot_id,
+        commit_ends_at,
+        reveal_ends_at,
+        slot_start,
+        slot_end,
+        reserve_price,
+        bid_deposit,
+        bid_count: 0,
+        revealed_bids: 0,
+        open_bids: 0,
+        highest_bid: 0,
+        second_bid: 0,
+        winner: None,
+        clearing_price: 0,
+        status: AuctionStatus::Open,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.slot_auction,
+    });
+
+    emit_cpi!(SlotAuctionOpened {
+        slot_auction: auction_key,
+        node: node_key,
+        slot_id,
+        commit_ends_at,
+        reveal_ends_at,
+        slot_start,
+        slot_end,
+        reserve_price,
+        bid_deposit,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
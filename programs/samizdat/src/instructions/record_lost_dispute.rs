@@ -0,0 +1,38 @@
+use crate::errors::SamizdatError;
+use crate::events::NodeDisputeLost;
+use crate::state::{NodeAccount, ProtocolConfig, NODE_ACCOUNT_SEED, PROTOCOL_CONFIG_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RecordLostDispute<'info> {
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ SamizdatError::Unauthorized,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+}
+
+pub fn process_record_lost_dispute(ctx: Context<RecordLostDispute>, reason: u16) -> Result<()> {
+    let node = &mut ctx.accounts.node_account;
+    node.record_lost_dispute()?;
+
+    emit_cpi!(NodeDisputeLost {
+        node: node.key(),
+        reason,
+        disputes_lost: node.disputes_lost,
+        reputation_bps: node.reputation_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,8 +1,11 @@
+use crate::events::NodeRegistered;
 use crate::state::{
-    GeoLocation, NodeAccount, NodeStatus, Resolution, ScreenSize, NODE_ACCOUNT_SEED,
+    GeoLocation, NodeAccount, NodeStatus, Resolution, ScreenSize, INITIAL_REPUTATION_BPS,
+    NODE_ACCOUNT_SEED,
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(node_id: u64)]
 pub struct RegisterNode<'info> {
@@ -25,6 +28,7 @@ pub struct RegisterNode<'info> {
 pub fn process_register_node(
     ctx: Context<RegisterNode>,
     node_id: u64,
+    signing_key: Pubkey,
     location: GeoLocation,
     screen_size: ScreenSize,
     resolution: Resolution,
@@ -33,21 +37,45 @@ pub fn process_register_node(
     estimated_footfall: u32,
     establishment_type: String,
 ) -> Result<()> {
+    let registered_at = Clock::get()?.unix_timestamp;
     ctx.accounts.node_account.set_inner(NodeAccount {
         authority: ctx.accounts.authority.key(),
         node_id,
+        signing_key,
         location,
         screen_size,
         resolution,
         landmarks,
         blocked_tag_mask,
+        required_tag_mask: 0,
+        min_bounty_per_play: 0,
+        requires_approval: false,
         estimated_footfall,
         establishment_type,
         total_plays: 0,
+        total_claims: 0,
+        timed_out_plays: 0,
+        disputes_lost: 0,
+        reputation_bps: INITIAL_REPUTATION_BPS,
         total_earnings: 0,
-        registered_at: Clock::get()?.unix_timestamp,
+        bonds_forfeited: 0,
+        registered_at,
         status: NodeStatus::Active,
+        suspension_reason: 0,
+        bookings: Vec::new(),
+        auctioned_slots: Vec::new(),
         bump: ctx.bumps.node_account,
     });
+
+    emit_cpi!(NodeRegistered {
+        node: ctx.accounts.node_account.key(),
+        authority: ctx.accounts.authority.key(),
+        node_id,
+        signing_key,
+        location,
+        screen_size,
+        timestamp: registered_at,
+    });
+
     Ok(())
 }
@@ -1,6 +1,8 @@
+use crate::events::PublisherRegistered;
 use crate::state::{PublisherAccount, PublisherStatus, PUBLISHER_SEED};
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RegisterPublisher<'info> {
     #[account(
@@ -27,8 +29,17 @@ pub fn process_register_publisher(ctx: Context<RegisterPublisher>) -> Result<()>
             total_spent: 0,
             registered_at: Clock::get()?.unix_timestamp,
             status: PublisherStatus::Active,
+            suspension_reason: 0,
+            node_cooldown: 0,
+            frequency_cap: None,
             bump: ctx.bumps.publisher_account,
         });
+
+        emit_cpi!(PublisherRegistered {
+            publisher: ctx.accounts.publisher_account.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: ctx.accounts.publisher_account.registered_at,
+        });
     }
 
     Ok(())
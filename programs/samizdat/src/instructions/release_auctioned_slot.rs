@@ -0,0 +1,95 @@
+use crate::errors::SamizdatError;
+use crate::events::AuctionedSlotReleased;
+use crate::state::{
+    AuctionStatus, CampaignAccount, CampaignStatus, NodeAccount, NodeDenylist, PublisherAccount,
+    PublisherStatus, SlotAuction, CAMPAIGN_SEED, NODE_ACCOUNT_SEED, NODE_DENYLIST_SEED,
+    PUBLISHER_SEED, SLOT_AUCTION_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseAuctionedSlot<'info> {
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, node_account.key().as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+        has_one = node_account,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    /// The auction's winning campaign
+    #[account(
+        seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
+        constraint = slot_auction.winner == Some(campaign_account.key()) @ SamizdatError::NotAuctionWinner,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(
+        seeds = [PUBLISHER_SEED, publisher_account.authority.as_ref()],
+        bump = publisher_account.bump,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    /// CHECK: The campaign's NodeDenylist PDA. It only exists once the
+    /// publisher creates a denylist, and is only read when owned by this
+    /// program.
+    #[account(
+        seeds = [NODE_DENYLIST_SEED, campaign_account.key().as_ref()],
+        bump,
+    )]
+    pub node_denylist: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn process_release_auctioned_slot(ctx: Context<ReleaseAuctionedSlot>) -> Result<()> {
+    let auction = &ctx.accounts.slot_auction;
+    require!(
+        auction.status == AuctionStatus::Settled,
+        SamizdatError::SlotNotClaimable
+    );
+
+    // The slot can only be taken back once the winner has lost the ability
+    // to claim it for good: paused or closed, out of flight for the rest of
+    // the slot, suspended, or barred from this node
+    let clock = Clock::get()?;
+    let campaign = &ctx.accounts.campaign_account;
+    let node = &ctx.accounts.node_account;
+    let earliest_claim = clock.unix_timestamp.max(auction.slot_start);
+    let denylisted = match NodeDenylist::load(&ctx.accounts.node_denylist)? {
+        Some(denylist) => denylist.contains(&node.key()) || denylist.contains(&node.authority),
+        None => false,
+    };
+    let can_claim = campaign.status == CampaignStatus::Active
+        && ctx.accounts.publisher_account.status == PublisherStatus::Active
+        && campaign.end_at.is_none_or(|end| earliest_claim < end)
+        && campaign.check_content_policy(node).is_ok()
+        && !denylisted;
+    require!(!can_claim, SamizdatError::WinnerCanStillClaim);
+
+    let auction_key = auction.key();
+    ctx.accounts.slot_auction.status = AuctionStatus::Released;
+    ctx.accounts
+        .node_account
+        .remove_auctioned_slot(&auction_key);
+
+    emit_cpi!(AuctionedSlotReleased {
+        slot_auction: auction_key,
+        node: ctx.accounts.node_account.key(),
+        winner: ctx.accounts.campaign_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::errors::SamizdatError;
+use crate::events::BidRevealed;
+use crate::state::{
+    AuctionStatus, Bid, CampaignAccount, CampaignStatus, SlotAuction, BID_SEED, CAMPAIGN_SEED,
+    SLOT_AUCTION_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    #[account(
+        mut,
+        seeds = [BID_SEED, slot_auction.key().as_ref(), campaign_account.key().as_ref()],
+        bump = bid.bump,
+        has_one = slot_auction,
+        has_one = campaign_account,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, slot_auction.node_account.as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(
+        seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+    let auction = &ctx.accounts.slot_auction;
+    let bid = &ctx.accounts.bid;
+    let campaign = &ctx.accounts.campaign_account;
+
+    // Validate the reveal window
+    require!(
+        auction.status == AuctionStatus::Open,
+        SamizdatError::AuctionNotOpen
+    );
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction.commit_ends_at
+            && clock.unix_timestamp < auction.reveal_ends_at,
+        SamizdatError::RevealNotOpen
+    );
+    require!(!bid.revealed, SamizdatError::BidAlreadyRevealed);
+    require!(
+        Bid::commitment(amount, &salt, &campaign.key()) == bid.commitment,
+        SamizdatError::CommitmentMismatch
+    );
+
+    // The bid is capped by the bounty so the budget reserved at claim time
+    // always covers the clearing price
+    require!(
+        campaign.status == CampaignStatus::Active,
+        SamizdatError::CampaignNotActive
+    );
+    require!(
+        amount >= auction.reserve_price && amount <= campaign.bounty_per_play,
+        SamizdatError::InvalidBid
+    );
+
+    let campaign_key = campaign.key();
+    ctx.accounts
+        .slot_auction
+        .record_reveal(campaign_key, amount)?;
+
+    // Return the deposit now that the bid is known
+    let deposit = ctx.accounts.bid.deposit;
+    if deposit > 0 {
+        **ctx
+            .accounts
+            .bid
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= deposit;
+        **ctx.accounts.authority.try_borrow_mut_lamports()? += deposit;
+    }
+
+    let bid = &mut ctx.accounts.bid;
+    bid.amount = amount;
+    bid.revealed = true;
+    bid.revealed_at = clock.unix_timestamp;
+    bid.deposit = 0;
+
+    emit_cpi!(BidRevealed {
+        bid: bid.key(),
+        slot_auction: bid.slot_auction,
+        campaign: campaign_key,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::errors::SamizdatError;
+use crate::events::CampaignApprovalRevoked;
+use crate::state::{ApprovedCampaign, NodeAccount, APPROVED_CAMPAIGN_SEED, NODE_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeCampaignApproval<'info> {
+    /// The campaign may already be closed, so the approval is located via
+    /// its stored campaign key rather than a campaign account.
+    #[account(
+        mut,
+        seeds = [
+            APPROVED_CAMPAIGN_SEED,
+            node_account.key().as_ref(),
+            approved_campaign.campaign_account.as_ref(),
+            &approved_campaign.content_hash,
+        ],
+        bump = approved_campaign.bump,
+        has_one = node_account,
+        close = authority,
+    )]
+    pub approved_campaign: Account<'info, ApprovedCampaign>,
+
+    #[account(
+        seeds = [NODE_ACCOUNT_SEED, authority.key().as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn process_revoke_campaign_approval(ctx: Context<RevokeCampaignApproval>) -> Result<()> {
+    // Plays already claimed under the approval can still be confirmed
+    let approval = &ctx.accounts.approved_campaign;
+    emit_cpi!(CampaignApprovalRevoked {
+        approved_campaign: approval.key(),
+        node: approval.node_account,
+        campaign: approval.campaign_account,
+        content_hash: approval.content_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::errors::SamizdatError;
+use crate::events::BookingSettled;
+use crate::state::{
+    Booking, CampaignAccount, NodeAccount, BOOKING_SEED, CAMPAIGN_SEED, NODE_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+
+/// Permissionless: anyone can settle a booking once its window has passed
+/// and each of its claims has been confirmed or timed out.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleBooking<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BOOKING_SEED,
+            campaign_account.key().as_ref(),
+            node_account.key().as_ref(),
+            &booking.booking_id.to_le_bytes(),
+        ],
+        bump = booking.bump,
+        has_one = campaign_account,
+        has_one = node_account,
+        has_one = authority,
+        close = authority,
+    )]
+    pub booking: Account<'info, Booking>,
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    /// CHECK: Publisher wallet that created the booking, receives its rent
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn process_settle_booking(ctx: Context<SettleBooking>) -> Result<()> {
+    // Claims made before end_at must be confirmed or timed out first, since
+    // both need the booking
+    let booking = &ctx.accounts.booking;
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= booking.end_at && booking.claims_resolved(),
+        SamizdatError::BookingNotEnded
+    );
+
+    // Refund the escrow of undelivered plays to the campaign budget
+    let released = booking.undelivered_escrow();
+    let campaign = &mut ctx.accounts.campaign_account;
+    campaign.release_budget(released);
+    campaign.open_bookings = campaign
+        .open_bookings
+        .checked_sub(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
+
+    // Free the node's window
+    let booking_key = booking.key();
+    ctx.accounts.node_account.remove_booking(&booking_key);
+
+    emit_cpi!(BookingSettled {
+        booking: booking_key,
+        campaign: booking.campaign_account,
+        node: booking.node_account,
+        guaranteed_plays: booking.guaranteed_plays,
+        plays_delivered: booking.plays_delivered,
+        released,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
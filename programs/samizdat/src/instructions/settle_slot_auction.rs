@@ -0,0 +1,59 @@
+use crate::errors::SamizdatError;
+use crate::events::SlotAuctionSettled;
+use crate::state::{AuctionStatus, NodeAccount, SlotAuction, NODE_ACCOUNT_SEED, SLOT_AUCTION_SEED};
+use anchor_lang::prelude::*;
+
+/// Permissionless: anyone can settle once the reveal window has closed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleSlotAuction<'info> {
+    #[account(
+        mut,
+        seeds = [SLOT_AUCTION_SEED, node_account.key().as_ref(), &slot_auction.slot_id.to_le_bytes()],
+        bump = slot_auction.bump,
+        has_one = node_account,
+    )]
+    pub slot_auction: Account<'info, SlotAuction>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn process_settle_slot_auction(ctx: Context<SettleSlotAuction>) -> Result<()> {
+    let auction = &mut ctx.accounts.slot_auction;
+    require!(
+        auction.status == AuctionStatus::Open,
+        SamizdatError::AuctionNotOpen
+    );
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= auction.reveal_ends_at,
+        SamizdatError::BiddingNotClosed
+    );
+
+    auction.settle();
+
+    // An unsold slot goes back to open claiming
+    if auction.winner.is_none() {
+        ctx.accounts
+            .node_account
+            .remove_auctioned_slot(&auction.key());
+    }
+
+    emit_cpi!(SlotAuctionSettled {
+        slot_auction: auction.key(),
+        winner: auction.winner,
+        clearing_price: auction.clearing_price,
+        bid_count: auction.bid_count,
+        revealed_bids: auction.revealed_bids,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::errors::SamizdatError;
+use crate::events::NodeSuspended;
+use crate::state::{
+    NodeAccount, NodeStatus, ProtocolConfig, NODE_ACCOUNT_SEED, PROTOCOL_CONFIG_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SuspendNode<'info> {
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ SamizdatError::Unauthorized,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+}
+
+pub fn process_suspend_node(ctx: Context<SuspendNode>, reason: u16) -> Result<()> {
+    let node = &mut ctx.accounts.node_account;
+    require!(
+        node.status != NodeStatus::Suspended,
+        SamizdatError::InvalidStatusTransition
+    );
+
+    node.status = NodeStatus::Suspended;
+    node.suspension_reason = reason;
+
+    emit_cpi!(NodeSuspended {
+        node: node.key(),
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::errors::SamizdatError;
+use crate::events::PublisherSuspended;
+use crate::state::{
+    ProtocolConfig, PublisherAccount, PublisherStatus, PROTOCOL_CONFIG_SEED, PUBLISHER_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SuspendPublisher<'info> {
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ SamizdatError::Unauthorized,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PUBLISHER_SEED, publisher_account.authority.as_ref()],
+        bump = publisher_account.bump,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+}
+
+pub fn process_suspend_publisher(ctx: Context<SuspendPublisher>, reason: u16) -> Result<()> {
+    let publisher = &mut ctx.accounts.publisher_account;
+    require!(
+        publisher.status != PublisherStatus::Suspended,
+        SamizdatError::InvalidStatusTransition
+    );
+
+    publisher.status = PublisherStatus::Suspended;
+    publisher.suspension_reason = reason;
+
+    emit_cpi!(PublisherSuspended {
+        publisher: publisher.key(),
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
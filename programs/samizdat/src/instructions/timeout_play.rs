@@ -1,21 +1,29 @@
 use crate::errors::SamizdatError;
+use crate::events::PlayTimedOut;
 use crate::state::{
-    CampaignAccount, PlayRecord, PlayStatus, CAMPAIGN_SEED, PLAY_RECORD_SEED, PLAY_TIMEOUT_SECONDS,
+    Booking, CampaignAccount, FrequencyTracker, NodeAccount, PlayRecord, PlayStatus,
+    ProtocolConfig, PublisherAccount, PublisherStatus, BOOKING_SEED, CAMPAIGN_SEED, FREQUENCY_SEED,
+    NODE_ACCOUNT_SEED, PLAY_RECORD_SEED, PROTOCOL_CONFIG_SEED, PUBLISHER_SEED,
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct TimeoutPlay<'info> {
+    /// Closed to the cranker: its rent and the cranker's share of the bond
+    /// are the crank reward.
     #[account(
         mut,
         seeds = [
             PLAY_RECORD_SEED,
             campaign_account.key().as_ref(),
-            play_record.node_account.as_ref(),
+            node_account.key().as_ref(),
             &play_record.nonce.to_le_bytes(),
         ],
         bump = play_record.bump,
         has_one = campaign_account,
+        has_one = node_account,
+        close = cranker,
     )]
     pub play_record: Account<'info, PlayRecord>,
 
@@ -23,8 +31,69 @@ pub struct TimeoutPlay<'info> {
         mut,
         seeds = [CAMPAIGN_SEED, campaign_account.publisher_account.as_ref(), &campaign_account.campaign_id.to_le_bytes()],
         bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
     )]
     pub campaign_account: Account<'info, CampaignAccount>,
+
+    /// Campaign's publisher; a suspension blocks confirmation, so the bond
+    /// of a play that timed out under it is refunded instead of forfeited.
+    #[account(
+        seeds = [PUBLISHER_SEED, publisher_account.authority.as_ref()],
+        bump = publisher_account.bump,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    /// Node that abandoned the claim; records the timeout and forfeited bond.
+    #[account(
+        mut,
+        seeds = [NODE_ACCOUNT_SEED, node_account.authority.as_ref(), &node_account.node_id.to_le_bytes()],
+        bump = node_account.bump,
+    )]
+    pub node_account: Account<'info, NodeAccount>,
+
+    /// CHECK: Node operator; receives the refunded bond when the publisher
+    /// is suspended. Address checked against the node account.
+    #[account(
+        mut,
+        address = node_account.authority @ SamizdatError::Unauthorized,
+    )]
+    pub operator: UncheckedAccount<'info>,
+
+    /// Publisher's plays on this node; the abandoned claim stops counting
+    /// against its frequency cap.
+    #[account(
+        mut,
+        seeds = [
+            FREQUENCY_SEED,
+            publisher_account.key().as_ref(),
+            node_account.key().as_ref(),
+        ],
+        bump = frequency_tracker.bump,
+    )]
+    pub frequency_tracker: Account<'info, FrequencyTracker>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Booking the play was claimed under; required for booked plays.
+    #[account(
+        mut,
+        seeds = [
+            BOOKING_SEED,
+            campaign_account.key().as_ref(),
+            node_account.key().as_ref(),
+            &booking.booking_id.to_le_bytes(),
+        ],
+        bump = booking.bump,
+    )]
+    pub booking: Option<Account<'info, Booking>>,
+
+    /// Anyone may crank an expired claim.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
 }
 
 pub fn process_timeout_play(ctx: Context<TimeoutPlay>) -> Result<()> {
@@ -39,17 +108,101 @@ pub fn process_timeout_play(ctx: Context<TimeoutPlay>) -> Result<()> {
     // Validate timeout has expired
     let clock = Clock::get()?;
     require!(
-        clock.unix_timestamp > play_record.claimed_at + PLAY_TIMEOUT_SECONDS,
+        clock.unix_timestamp > play_record.expires_at,
         SamizdatError::TimeoutNotExpired
     );
 
-    // Restore plays_remaining
+    let bond = ctx.accounts.play_record.bond_amount;
+    if ctx.accounts.publisher_account.status != PublisherStatus::Active {
+        // The publisher's suspension kept the node from confirming, so the
+        // claim was not abandoned: the bond goes back to the operator and
+        // the node's reputation is untouched
+        **ctx
+            .accounts
+            .play_record
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= bond;
+        **ctx.accounts.operator.try_borrow_mut_lamports()? += bond;
+        return release_claim(ctx, 0, 0, clock.unix_timestamp);
+    }
+
+    // Count the abandoned claim against the node's reputation
+    ctx.accounts.node_account.record_timed_out_play()?;
+
+    // Forfeit the claim bond, which alone funds the crank reward: the
+    // campaign's share moves into the campaign account now, the cranker's
+    // share leaves with the PlayRecord on close
+    let crank_reward = ctx.accounts.protocol_config.crank_reward_for(bond);
+    if bond > 0 {
+        let campaign_share = bond - crank_reward;
+        **ctx
+            .accounts
+            .play_record
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= campaign_share;
+        **ctx
+            .accounts
+            .campaign_account
+            .to_account_info()
+            .try_borrow_mut_lamports()? += campaign_share;
+
+        let node = &mut ctx.accounts.node_account;
+        node.bonds_forfeited = node
+            .bonds_forfeited
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+    }
+
+    release_claim(ctx, crank_reward, bond, clock.unix_timestamp)
+}
+
+/// Returns the play slot and reserved budget, releases the outstanding claim
+/// and emits `PlayTimedOut`.
+fn release_claim(
+    ctx: Context<TimeoutPlay>,
+    crank_reward: u64,
+    bond_forfeited: u64,
+    now: i64,
+) -> Result<()> {
+    // Return the play slot, reserved budget and pacing, release the
+    // outstanding claim. A booked play stays escrowed by its booking, which
+    // can take another claim in its place, and never counted toward pacing.
+    let claimed_at = ctx.accounts.play_record.claimed_at;
     let campaign = &mut ctx.accounts.campaign_account;
-    campaign.plays_remaining = campaign.plays_remaining.checked_add(1).unwrap();
+    match ctx.accounts.play_record.booking {
+        Some(booking_key) => {
+            let booking = ctx
+                .accounts
+                .booking
+                .as_mut()
+                .filter(|booking| booking.key() == booking_key)
+                .ok_or(SamizdatError::BookingMismatch)?;
+            booking.release_claim();
+        }
+        None => {
+            campaign.cancel_play(ctx.accounts.play_record.reserved_amount)?;
+            campaign.release_daily_play(claimed_at);
+            ctx.accounts.frequency_tracker.release_claim(claimed_at);
+        }
+    }
+    let campaign = &mut ctx.accounts.campaign_account;
+    campaign.outstanding_claims = campaign
+        .outstanding_claims
+        .checked_sub(1)
+        .ok_or(SamizdatError::ArithmeticOverflow)?;
 
-    // Update PlayRecord
-    let play_record = &mut ctx.accounts.play_record;
-    play_record.status = PlayStatus::TimedOut;
+    // The PlayRecord itself is closed to the cranker on exit
+    let play_record = &ctx.accounts.play_record;
+    emit_cpi!(PlayTimedOut {
+        play_record: play_record.key(),
+        campaign: play_record.campaign_account,
+        node: play_record.node_account,
+        cranker: ctx.accounts.cranker.key(),
+        crank_reward,
+        bond_forfeited,
+        claimed_at: play_record.claimed_at,
+        timestamp: now,
+    });
 
     Ok(())
 }
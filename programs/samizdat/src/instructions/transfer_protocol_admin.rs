@@ -0,0 +1,34 @@
+use crate::errors::SamizdatError;
+use crate::events::ProtocolAdminProposed;
+use crate::state::{ProtocolConfig, PROTOCOL_CONFIG_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferProtocolAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ SamizdatError::Unauthorized,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn process_transfer_protocol_admin(
+    ctx: Context<TransferProtocolAdmin>,
+    new_admin: Option<Pubkey>,
+) -> Result<()> {
+    // Step one of two: the proposed admin must accept before taking over.
+    // Passing None cancels a pending transfer.
+    ctx.accounts.protocol_config.pending_admin = new_admin;
+
+    emit_cpi!(ProtocolAdminProposed {
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,9 +1,13 @@
+use crate::discovery;
 use crate::errors::SamizdatError;
+use crate::events::CampaignUpdated;
 use crate::state::{
-    CampaignAccount, CampaignStatus, PublisherAccount, TargetFilters, CAMPAIGN_SEED, PUBLISHER_SEED,
+    geo_cells, CampaignAccount, CampaignStatus, PricingModel, PublisherAccount, TargetFilters,
+    CAMPAIGN_SEED, PUBLISHER_SEED,
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateCampaign<'info> {
     #[account(
@@ -21,20 +25,32 @@ pub struct UpdateCampaign<'info> {
     )]
     pub publisher_account: Account<'info, PublisherAccount>,
 
+    /// Pays for GeoIndex growth and receives the rent of shrinking ones.
+    #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn process_update_campaign(
-    ctx: Context<UpdateCampaign>,
+#[allow(clippy::too_many_arguments)]
+pub fn process_update_campaign<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateCampaign<'info>>,
     tag_mask: Option<u64>,
     target_filters: Option<TargetFilters>,
     status: Option<CampaignStatus>,
+    start_at: Option<Option<i64>>,
+    end_at: Option<Option<i64>>,
+    daily_play_cap: Option<Option<u64>>,
+    bounty_per_play: Option<u64>,
 ) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign_account;
 
-    // Closed campaigns are immutable
+    // Closed, draining and expired campaigns are immutable
     require!(
-        campaign.status != CampaignStatus::Closed,
+        !matches!(
+            campaign.status,
+            CampaignStatus::Closed | CampaignStatus::Draining | CampaignStatus::Expired
+        ),
         SamizdatError::CampaignNotActive
     );
 
@@ -42,16 +58,73 @@ pub fn process_update_campaign(
         campaign.tag_mask = mask;
     }
     if let Some(filters) = target_filters {
+        filters.validate()?;
+        // Move the campaign between GeoIndexes as its cells change
+        let cells = geo_cells(&filters.geo_regions);
+        campaign.geo_cells = discovery::update_geo_indexes(
+            campaign.key(),
+            &campaign.geo_cells,
+            &cells,
+            ctx.remaining_accounts,
+            &ctx.accounts.authority.to_account_info(),
+            Some(&ctx.accounts.system_program.to_account_info()),
+        )?;
         campaign.target_filters = filters;
     }
     if let Some(s) = status {
-        // Closed is only set via close_campaign instruction
+        // Closed and Draining are only set via close_campaign instruction,
+        // Expired via expire_campaign
         require!(
-            !matches!(s, CampaignStatus::Closed),
+            !matches!(
+                s,
+                CampaignStatus::Closed | CampaignStatus::Draining | CampaignStatus::Expired
+            ),
             SamizdatError::InvalidStatusTransition
         );
         campaign.status = s;
     }
+    if start_at.is_some() || end_at.is_some() {
+        // Some(None) removes that end of the flight window
+        let start_at = start_at.unwrap_or(campaign.start_at);
+        let end_at = end_at.unwrap_or(campaign.end_at);
+        CampaignAccount::validate_flight_window(start_at, end_at)?;
+        if let Some(end) = end_at {
+            require!(
+                end > Clock::get()?.unix_timestamp,
+                SamizdatError::InvalidFlightWindow
+            );
+        }
+        campaign.start_at = start_at;
+        campaign.end_at = end_at;
+    }
+    if let Some(cap) = daily_play_cap {
+        // Some(None) removes the cap; a cap of zero is rejected as at creation
+        require!(cap != Some(0), SamizdatError::InvalidPlays);
+        campaign.daily_play_cap = cap;
+    }
+    if let Some(ceiling) = bounty_per_play {
+        // Only a Cpm ceiling can move: PerPlay budgets are funded per play
+        // and PerSecond derives it from its rate. Outstanding claims keep
+        // the reservation they were made with, so the new ceiling only
+        // applies to later claims.
+        require!(
+            matches!(campaign.pricing, PricingModel::Cpm { .. }),
+            SamizdatError::InvalidPricing
+        );
+        require!(ceiling > 0, SamizdatError::InvalidBounty);
+        campaign.bounty_per_play = ceiling;
+    }
+
+    emit_cpi!(CampaignUpdated {
+        campaign: campaign.key(),
+        tag_mask: campaign.tag_mask,
+        status: campaign.status,
+        start_at: campaign.start_at,
+        end_at: campaign.end_at,
+        daily_play_cap: campaign.daily_play_cap,
+        bounty_per_play: campaign.bounty_per_play,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
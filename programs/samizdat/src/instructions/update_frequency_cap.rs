@@ -0,0 +1,45 @@
+use crate::errors::SamizdatError;
+use crate::events::FrequencyCapUpdated;
+use crate::state::{FrequencyCap, PublisherAccount, MAX_NODE_COOLDOWN_SECONDS, PUBLISHER_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFrequencyCap<'info> {
+    #[account(
+        mut,
+        seeds = [PUBLISHER_SEED, authority.key().as_ref()],
+        bump = publisher_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn process_update_frequency_cap(
+    ctx: Context<UpdateFrequencyCap>,
+    node_cooldown: i64,
+    frequency_cap: Option<FrequencyCap>,
+) -> Result<()> {
+    require!(
+        (0..=MAX_NODE_COOLDOWN_SECONDS).contains(&node_cooldown),
+        SamizdatError::InvalidAmount
+    );
+    if let Some(cap) = &frequency_cap {
+        cap.validate()?;
+    }
+
+    let publisher = &mut ctx.accounts.publisher_account;
+    publisher.node_cooldown = node_cooldown;
+    publisher.frequency_cap = frequency_cap;
+
+    emit_cpi!(FrequencyCapUpdated {
+        publisher: publisher.key(),
+        node_cooldown,
+        frequency_cap,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
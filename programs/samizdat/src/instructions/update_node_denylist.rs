@@ -0,0 +1,80 @@
+use crate::errors::SamizdatError;
+use crate::events::NodeDenylistUpdated;
+use crate::state::{
+    CampaignAccount, CampaignStatus, NodeDenylist, PublisherAccount, CAMPAIGN_SEED,
+    MAX_NODE_DENYLIST_ENTRIES, NODE_DENYLIST_SEED, PUBLISHER_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateNodeDenylist<'info> {
+    /// Created on the first update at full size, so it never needs to grow.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + NodeDenylist::INIT_SPACE,
+        seeds = [NODE_DENYLIST_SEED, campaign_account.key().as_ref()],
+        bump,
+    )]
+    pub node_denylist: Account<'info, NodeDenylist>,
+
+    #[account(
+        seeds = [CAMPAIGN_SEED, publisher_account.key().as_ref(), &campaign_account.campaign_id.to_le_bytes()],
+        bump = campaign_account.bump,
+        has_one = publisher_account @ SamizdatError::PublisherMismatch,
+    )]
+    pub campaign_account: Account<'info, CampaignAccount>,
+
+    #[account(
+        seeds = [PUBLISHER_SEED, authority.key().as_ref()],
+        bump = publisher_account.bump,
+        has_one = authority @ SamizdatError::Unauthorized,
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_update_node_denylist(
+    ctx: Context<UpdateNodeDenylist>,
+    add: Vec<Pubkey>,
+    remove: Vec<Pubkey>,
+) -> Result<()> {
+    // Same as update_campaign: closed, draining and expired campaigns are
+    // immutable
+    require!(
+        !matches!(
+            ctx.accounts.campaign_account.status,
+            CampaignStatus::Closed | CampaignStatus::Draining | CampaignStatus::Expired
+        ),
+        SamizdatError::CampaignNotActive
+    );
+
+    let denylist = &mut ctx.accounts.node_denylist;
+    denylist.campaign_account = ctx.accounts.campaign_account.key();
+    denylist.authority = ctx.accounts.authority.key();
+    denylist.bump = ctx.bumps.node_denylist;
+    for key in &remove {
+        denylist.remove(key);
+    }
+    for key in add {
+        denylist.insert(key);
+    }
+    require!(
+        denylist.entries.len() <= MAX_NODE_DENYLIST_ENTRIES,
+        SamizdatError::TooManyDenylistEntries
+    );
+
+    emit_cpi!(NodeDenylistUpdated {
+        node_denylist: denylist.key(),
+        campaign: denylist.campaign_account,
+        entries: denylist.entries.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
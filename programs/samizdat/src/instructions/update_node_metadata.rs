@@ -1,7 +1,9 @@
 use crate::errors::SamizdatError;
+use crate::events::NodeUpdated;
 use crate::state::{GeoLocation, NodeAccount, NodeStatus, NODE_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateNodeMetadata<'info> {
     #[account(
@@ -15,12 +17,17 @@ pub struct UpdateNodeMetadata<'info> {
     pub authority: Signer<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_update_node_metadata(
     ctx: Context<UpdateNodeMetadata>,
     location: Option<GeoLocation>,
     estimated_footfall: Option<u32>,
     blocked_tag_mask: Option<u64>,
+    required_tag_mask: Option<u64>,
+    min_bounty_per_play: Option<u64>,
+    requires_approval: Option<bool>,
     status: Option<NodeStatus>,
+    signing_key: Option<Pubkey>,
 ) -> Result<()> {
     let node = &mut ctx.accounts.node_account;
 
@@ -33,9 +40,39 @@ pub fn process_update_node_metadata(
     if let Some(mask) = blocked_tag_mask {
         node.blocked_tag_mask = mask;
     }
+    if let Some(mask) = required_tag_mask {
+        node.required_tag_mask = mask;
+    }
+    if let Some(floor) = min_bounty_per_play {
+        node.min_bounty_per_play = floor;
+    }
+    if let Some(required) = requires_approval {
+        node.requires_approval = required;
+    }
     if let Some(s) = status {
+        // Suspension is admin-only: operators can neither set nor lift it
+        require!(
+            s != NodeStatus::Suspended && node.status != NodeStatus::Suspended,
+            SamizdatError::InvalidStatusTransition
+        );
         node.status = s;
     }
+    if let Some(key) = signing_key {
+        node.signing_key = key;
+    }
+
+    emit_cpi!(NodeUpdated {
+        node: node.key(),
+        location: node.location,
+        estimated_footfall: node.estimated_footfall,
+        blocked_tag_mask: node.blocked_tag_mask,
+        required_tag_mask: node.required_tag_mask,
+        min_bounty_per_play: node.min_bounty_per_play,
+        requires_approval: node.requires_approval,
+        status: node.status,
+        signing_key: node.signing_key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
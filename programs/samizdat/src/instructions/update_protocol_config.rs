@@ -0,0 +1,66 @@
+use crate::errors::SamizdatError;
+use crate::events::ProtocolConfigUpdated;
+use crate::state::{ProtocolConfig, BPS_DENOMINATOR, PROTOCOL_CONFIG_SEED};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ SamizdatError::Unauthorized,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn process_update_protocol_config(
+    ctx: Context<UpdateProtocolConfig>,
+    treasury: Option<Pubkey>,
+    fee_bps: Option<u16>,
+    crank_reward_bps: Option<u16>,
+    claim_bond: Option<u64>,
+    play_timeout: Option<i64>,
+    paused: Option<bool>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+
+    if let Some(t) = treasury {
+        config.treasury = t;
+    }
+    if let Some(fee) = fee_bps {
+        require!(fee <= BPS_DENOMINATOR, SamizdatError::InvalidFee);
+        config.fee_bps = fee;
+    }
+    if let Some(reward) = crank_reward_bps {
+        require!(reward <= BPS_DENOMINATOR, SamizdatError::InvalidFee);
+        config.crank_reward_bps = reward;
+    }
+    if let Some(bond) = claim_bond {
+        config.claim_bond = bond;
+    }
+    if let Some(timeout) = play_timeout {
+        require!(timeout > 0, SamizdatError::InvalidTimeout);
+        config.play_timeout = timeout;
+    }
+    if let Some(p) = paused {
+        config.paused = p;
+    }
+
+    let config = &ctx.accounts.protocol_config;
+    emit_cpi!(ProtocolConfigUpdated {
+        admin: config.admin,
+        treasury: config.treasury,
+        fee_bps: config.fee_bps,
+        crank_reward_bps: config.crank_reward_bps,
+        claim_bond: config.claim_bond,
+        play_timeout: config.play_timeout,
+        paused: config.paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
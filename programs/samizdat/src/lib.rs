@@ -1,12 +1,18 @@
 use anchor_lang::prelude::*;
 
 pub mod definitions;
+pub mod discovery;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod vault;
 
 use instructions::*;
-use state::{CampaignStatus, GeoLocation, NodeStatus, Resolution, ScreenSize, TargetFilters};
+use state::{
+    CampaignStatus, Creative, FrequencyCap, GeoLocation, NodeStatus, PricingModel, Resolution,
+    ScreenSize, TargetFilters,
+};
 
 declare_id!("EdiAD6MqML7e4DnfR85Pdbj15m7zyQqGaM8koJhGQq1j");
 
@@ -20,25 +26,33 @@ pub mod samizdat {
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn create_campaign(
-        ctx: Context<CreateCampaign>,
+    pub fn create_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateCampaign<'info>>,
         campaign_id: u64,
-        cids: Vec<String>,
+        creatives: Vec<Creative>,
         bounty_per_play: u64,
+        pricing: PricingModel,
         total_plays: u64,
         tag_mask: u64,
         target_filters: TargetFilters,
         claim_cooldown: i64,
+        start_at: Option<i64>,
+        end_at: Option<i64>,
+        daily_play_cap: Option<u64>,
     ) -> Result<()> {
         instructions::process_create_campaign(
             ctx,
             campaign_id,
-            cids,
+            creatives,
             bounty_per_play,
+            pricing,
             total_plays,
             tag_mask,
             target_filters,
             claim_cooldown,
+            start_at,
+            end_at,
+            daily_play_cap,
         )
     }
 
@@ -46,31 +60,68 @@ pub mod samizdat {
         instructions::process_fund_campaign(ctx, amount)
     }
 
-    pub fn update_campaign(
-        ctx: Context<UpdateCampaign>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateCampaign<'info>>,
         tag_mask: Option<u64>,
         target_filters: Option<TargetFilters>,
         status: Option<CampaignStatus>,
+        start_at: Option<Option<i64>>,
+        end_at: Option<Option<i64>>,
+        daily_play_cap: Option<Option<u64>>,
+        bounty_per_play: Option<u64>,
     ) -> Result<()> {
-        instructions::process_update_campaign(ctx, tag_mask, target_filters, status)
+        instructions::process_update_campaign(
+            ctx,
+            tag_mask,
+            target_filters,
+            status,
+            start_at,
+            end_at,
+            daily_play_cap,
+            bounty_per_play,
+        )
     }
 
-    pub fn add_cids_to_campaign(
-        ctx: Context<AddCidsToCampaign>,
-        new_cids: Vec<String>,
+    pub fn add_creatives_to_campaign(
+        ctx: Context<AddCreativesToCampaign>,
+        new_creatives: Vec<Creative>,
     ) -> Result<()> {
-        instructions::process_add_cids_to_campaign(ctx, new_cids)
+        instructions::process_add_creatives_to_campaign(ctx, new_creatives)
     }
 
-    pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
+    pub fn close_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseCampaign<'info>>,
+    ) -> Result<()> {
         instructions::process_close_campaign(ctx)
     }
 
+    pub fn update_frequency_cap(
+        ctx: Context<UpdateFrequencyCap>,
+        node_cooldown: i64,
+        frequency_cap: Option<FrequencyCap>,
+    ) -> Result<()> {
+        instructions::process_update_frequency_cap(ctx, node_cooldown, frequency_cap)
+    }
+
+    pub fn update_node_denylist(
+        ctx: Context<UpdateNodeDenylist>,
+        add: Vec<Pubkey>,
+        remove: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::process_update_node_denylist(ctx, add, remove)
+    }
+
+    pub fn close_node_denylist(ctx: Context<CloseNodeDenylist>) -> Result<()> {
+        instructions::process_close_node_denylist(ctx)
+    }
+
     // Operator Instructions
     #[allow(clippy::too_many_arguments)]
     pub fn register_node(
         ctx: Context<RegisterNode>,
         node_id: u64,
+        signing_key: Pubkey,
         location: GeoLocation,
         screen_size: ScreenSize,
         resolution: Resolution,
@@ -82,6 +133,7 @@ pub mod samizdat {
         instructions::process_register_node(
             ctx,
             node_id,
+            signing_key,
             location,
             screen_size,
             resolution,
@@ -92,37 +144,212 @@ pub mod samizdat {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_node_metadata(
         ctx: Context<UpdateNodeMetadata>,
         location: Option<GeoLocation>,
         estimated_footfall: Option<u32>,
         blocked_tag_mask: Option<u64>,
+        required_tag_mask: Option<u64>,
+        min_bounty_per_play: Option<u64>,
+        requires_approval: Option<bool>,
         status: Option<NodeStatus>,
+        signing_key: Option<Pubkey>,
     ) -> Result<()> {
         instructions::process_update_node_metadata(
             ctx,
             location,
             estimated_footfall,
             blocked_tag_mask,
+            required_tag_mask,
+            min_bounty_per_play,
+            requires_approval,
             status,
+            signing_key,
         )
     }
 
+    pub fn approve_campaign(ctx: Context<ApproveCampaign>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::process_approve_campaign(ctx, content_hash)
+    }
+
+    pub fn revoke_campaign_approval(ctx: Context<RevokeCampaignApproval>) -> Result<()> {
+        instructions::process_revoke_campaign_approval(ctx)
+    }
+
     // Play Cycle Instructions
     pub fn claim_campaign(
         ctx: Context<ClaimCampaign>,
-        cid_index: u8,
+        creative_index: u8,
         claim_nonce: i64,
+        allowlist_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::process_claim_campaign(ctx, creative_index, claim_nonce, allowlist_proof)
+    }
+
+    pub fn confirm_play(
+        ctx: Context<ConfirmPlay>,
+        proof_timestamp: i64,
+        duration_ms: Option<u32>,
+    ) -> Result<()> {
+        instructions::process_confirm_play(ctx, proof_timestamp, duration_ms)
+    }
+
+    // Slot Auction Instructions
+    #[allow(clippy::too_many_arguments)]
+    pub fn open_slot_auction(
+        ctx: Context<OpenSlotAuction>,
+        slot_id: u64,
+        commit_ends_at: i64,
+        reveal_ends_at: i64,
+        slot_start: i64,
+        slot_end: i64,
+        reserve_price: u64,
+        bid_deposit: u64,
+    ) -> Result<()> {
+        instructions::process_open_slot_auction(
+            ctx,
+            slot_id,
+            commit_ends_at,
+            reveal_ends_at,
+            slot_start,
+            slot_end,
+            reserve_price,
+            bid_deposit,
+        )
+    }
+
+    pub fn commit_bid(
+        ctx: Context<CommitBid>,
+        commitment: [u8; 32],
+        allowlist_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::process_commit_bid(ctx, commitment, allowlist_proof)
+    }
+
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        instructions::process_reveal_bid(ctx, amount, salt)
+    }
+
+    pub fn settle_slot_auction(ctx: Context<SettleSlotAuction>) -> Result<()> {
+        instructions::process_settle_slot_auction(ctx)
+    }
+
+    pub fn release_auctioned_slot(ctx: Context<ReleaseAuctionedSlot>) -> Result<()> {
+        instructions::process_release_auctioned_slot(ctx)
+    }
+
+    pub fn close_bid(ctx: Context<CloseBid>) -> Result<()> {
+        instructions::process_close_bid(ctx)
+    }
+
+    pub fn close_slot_auction(ctx: Context<CloseSlotAuction>) -> Result<()> {
+        instructions::process_close_slot_auction(ctx)
+    }
+
+    // Booking Instructions
+    pub fn create_booking(
+        ctx: Context<CreateBooking>,
+        booking_id: u64,
+        start_at: i64,
+        end_at: i64,
+        guaranteed_plays: u64,
+        price_per_play: u64,
     ) -> Result<()> {
-        instructions::process_claim_campaign(ctx, cid_index, claim_nonce)
+        instructions::process_create_booking(
+            ctx,
+            booking_id,
+            start_at,
+            end_at,
+            guaranteed_plays,
+            price_per_play,
+        )
+    }
+
+    pub fn accept_booking(ctx: Context<AcceptBooking>) -> Result<()> {
+        instructions::process_accept_booking(ctx)
     }
 
-    pub fn confirm_play(ctx: Context<ConfirmPlay>) -> Result<()> {
-        instructions::process_confirm_play(ctx)
+    pub fn cancel_booking(ctx: Context<CancelBooking>) -> Result<()> {
+        instructions::process_cancel_booking(ctx)
+    }
+
+    pub fn settle_booking(ctx: Context<SettleBooking>) -> Result<()> {
+        instructions::process_settle_booking(ctx)
     }
 
     // Public Instructions
     pub fn timeout_play(ctx: Context<TimeoutPlay>) -> Result<()> {
         instructions::process_timeout_play(ctx)
     }
+
+    pub fn close_play_record(ctx: Context<ClosePlayRecord>) -> Result<()> {
+        instructions::process_close_play_record(ctx)
+    }
+
+    pub fn expire_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireCampaign<'info>>,
+    ) -> Result<()> {
+        instructions::process_expire_campaign(ctx)
+    }
+
+    // Admin Instructions
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        treasury: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::process_initialize_protocol_config(ctx, treasury, fee_bps)
+    }
+
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        treasury: Option<Pubkey>,
+        fee_bps: Option<u16>,
+        crank_reward_bps: Option<u16>,
+        claim_bond: Option<u64>,
+        play_timeout: Option<i64>,
+        paused: Option<bool>,
+    ) -> Result<()> {
+        instructions::process_update_protocol_config(
+            ctx,
+            treasury,
+            fee_bps,
+            crank_reward_bps,
+            claim_bond,
+            play_timeout,
+            paused,
+        )
+    }
+
+    pub fn transfer_protocol_admin(
+        ctx: Context<TransferProtocolAdmin>,
+        new_admin: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::process_transfer_protocol_admin(ctx, new_admin)
+    }
+
+    pub fn accept_protocol_admin(ctx: Context<AcceptProtocolAdmin>) -> Result<()> {
+        instructions::process_accept_protocol_admin(ctx)
+    }
+
+    pub fn suspend_publisher(ctx: Context<SuspendPublisher>, reason: u16) -> Result<()> {
+        instructions::process_suspend_publisher(ctx, reason)
+    }
+
+    pub fn reinstate_publisher(ctx: Context<ReinstatePublisher>) -> Result<()> {
+        instructions::process_reinstate_publisher(ctx)
+    }
+
+    pub fn suspend_node(ctx: Context<SuspendNode>, reason: u16) -> Result<()> {
+        instructions::process_suspend_node(ctx, reason)
+    }
+
+    pub fn reinstate_node(ctx: Context<ReinstateNode>) -> Result<()> {
+        instructions::process_reinstate_node(ctx)
+    }
+
+    pub fn record_lost_dispute(ctx: Context<RecordLostDispute>, reason: u16) -> Result<()> {
+        instructions::process_record_lost_dispute(ctx, reason)
+    }
 }
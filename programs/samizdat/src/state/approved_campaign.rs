@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// An operator's approval for one creative of a campaign to play on a node
+/// that has `requires_approval` set.
+/// Seeded by [APPROVED_CAMPAIGN_SEED, node.key(), campaign.key(), content_hash].
+#[account]
+#[derive(InitSpace)]
+pub struct ApprovedCampaign {
+    pub node_account: Pubkey,
+    pub campaign_account: Pubkey,
+    /// Creative.content_hash of the approved creative
+    pub content_hash: [u8; 32],
+    pub approved_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,38 @@
+use super::shared::{BPS_DENOMINATOR, UNREVEALED_BID_PENALTY_BPS};
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+/// One campaign's sealed bid on a SlotAuction.
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub slot_auction: Pubkey,
+    pub campaign_account: Pubkey,
+    /// Publisher authority that paid the rent and deposit and receives them back
+    pub authority: Pubkey,
+    /// sha256(amount LE || salt || campaign_account)
+    pub commitment: [u8; 32],
+    /// Lamports held in this account until the bid is revealed
+    pub deposit: u64,
+    /// Bid price, 0 until revealed
+    pub amount: u64,
+    pub revealed: bool,
+    pub committed_at: i64,
+    pub revealed_at: i64,
+    pub bump: u8,
+}
+
+impl Bid {
+    /// Hash a bid the way bidders commit to it off-chain. Binding the
+    /// campaign stops a rival from copying a commitment and revealing the
+    /// same amount once the original is revealed.
+    pub fn commitment(amount: u64, salt: &[u8; 32], campaign: &Pubkey) -> [u8; 32] {
+        hashv(&[&amount.to_le_bytes()[..], salt, campaign.as_ref()]).to_bytes()
+    }
+
+    /// Part of the deposit still held by an unrevealed bid that goes to the
+    /// node operator, rounded down.
+    pub fn forfeited_deposit(&self) -> u64 {
+        (self.deposit as u128 * UNREVEALED_BID_PENALTY_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
@@ -0,0 +1,76 @@
+use super::shared::BookingStatus;
+use crate::errors::SamizdatError;
+use anchor_lang::prelude::*;
+
+/// Reserved inventory: a guaranteed number of plays of one campaign on one
+/// node during a time window, paid at a fixed price from budget escrowed in
+/// the campaign vault.
+#[account]
+#[derive(InitSpace)]
+pub struct Booking {
+    pub campaign_account: Pubkey,
+    pub node_account: Pubkey,
+    /// Publisher wallet that proposed the booking and receives the rent back
+    pub authority: Pubkey,
+    pub booking_id: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub guaranteed_plays: u64,
+    pub price_per_play: u64,
+    /// Claims made under the booking, capped at guaranteed_plays
+    pub plays_claimed: u64,
+    /// Claims confirmed and paid
+    pub plays_delivered: u64,
+    pub status: BookingStatus,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Booking {
+    /// Budget held for the whole booking.
+    pub fn escrow(guaranteed_plays: u64, price_per_play: u64) -> Result<u64> {
+        Ok(guaranteed_plays
+            .checked_mul(price_per_play)
+            .ok_or(SamizdatError::ArithmeticOverflow)?)
+    }
+
+    /// Escrow still held for plays that were never delivered.
+    pub fn undelivered_escrow(&self) -> u64 {
+        self.guaranteed_plays.saturating_sub(self.plays_delivered) * self.price_per_play
+    }
+
+    /// Count a claim under the booking at `unix_timestamp`, returning the
+    /// price the play is paid at.
+    pub fn record_claim(&mut self, unix_timestamp: i64) -> Result<u64> {
+        require!(
+            self.status == BookingStatus::Active
+                && unix_timestamp >= self.start_at
+                && unix_timestamp < self.end_at,
+            SamizdatError::BookingNotActive
+        );
+        require!(
+            self.plays_claimed < self.guaranteed_plays,
+            SamizdatError::BookingFulfilled
+        );
+        self.plays_claimed += 1;
+        Ok(self.price_per_play)
+    }
+
+    /// Give back the claim of a booked play that timed out.
+    pub fn release_claim(&mut self) {
+        self.plays_claimed = self.plays_claimed.saturating_sub(1);
+    }
+
+    /// Whether every claim under the booking has been confirmed or timed out.
+    pub fn claims_resolved(&self) -> bool {
+        self.plays_claimed == self.plays_delivered
+    }
+
+    pub fn record_delivery(&mut self) -> Result<()> {
+        self.plays_delivered = self
+            .plays_delivered
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
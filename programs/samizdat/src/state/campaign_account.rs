@@ -1,4 +1,10 @@
-use super::shared::{CampaignStatus, TargetFilters, MAX_CIDS, MAX_CID_LENGTH};
+use super::node_account::NodeAccount;
+use super::node_lists::{allowlist_leaf, allowlist_root, NodeDenylist};
+use super::shared::{
+    CampaignStatus, Creative, PricingModel, TargetFilters, GEOHASH_PRECISION,
+    MAX_CAMPAIGN_GEO_CELLS, MAX_CREATIVES, SECONDS_PER_DAY,
+};
+use crate::errors::SamizdatError;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -6,16 +12,242 @@ use anchor_lang::prelude::*;
 pub struct CampaignAccount {
     pub publisher_account: Pubkey,
     pub campaign_id: u64,
-    #[max_len(MAX_CIDS, MAX_CID_LENGTH)]
-    pub cids: Vec<String>,
+    /// SPL mint bounties are paid in; None pays in native SOL lamports
+    pub mint: Option<Pubkey>,
+    #[max_len(MAX_CREATIVES)]
+    pub creatives: Vec<Creative>,
+    /// Flat bounty for PerPlay campaigns; the most one play can cost otherwise
     pub bounty_per_play: u64,
+    pub pricing: PricingModel,
+    /// Claims left for PerPlay campaigns; unused otherwise
     pub plays_remaining: u64,
     pub plays_completed: u64,
+    /// PlayRecords still in Claimed status; close_campaign waits for this to reach zero
+    pub outstanding_claims: u64,
+    /// Vault budget held for outstanding claims and for the unfulfilled
+    /// plays of open bookings
+    pub reserved_budget: u64,
+    /// Bookings not yet settled or cancelled; close_campaign waits for these too
+    pub open_bookings: u32,
     pub tag_mask: u64,
     pub target_filters: TargetFilters,
+    /// Geohash cells whose GeoIndex lists this campaign, sorted; empty
+    /// without geo regions
+    #[max_len(MAX_CAMPAIGN_GEO_CELLS, GEOHASH_PRECISION)]
+    pub geo_cells: Vec<String>,
     pub status: CampaignStatus,
     /// Minimum seconds between consecutive claims by the same node
     pub claim_cooldown: i64,
+    /// Flight start; claims before this timestamp are rejected
+    pub start_at: Option<i64>,
+    /// Flight end; claims at or after this timestamp are rejected
+    pub end_at: Option<i64>,
+    /// Maximum claims per UTC day; None for no pacing
+    pub daily_play_cap: Option<u64>,
+    /// UTC day number (unix_timestamp / 86400) that plays_today counts
+    pub pacing_day: i64,
+    pub plays_today: u64,
     pub created_at: i64,
     pub bump: u8,
 }
+
+impl CampaignAccount {
+    /// Require the campaign's content policy and target filters to accept
+    /// `node` at `unix_timestamp`.
+    pub fn check_node_eligibility(&self, node: &NodeAccount, unix_timestamp: i64) -> Result<()> {
+        self.check_content_policy(node)?;
+
+        // Campaign target filters
+        let filters = &self.target_filters;
+        if let Some(min) = filters.min_footfall {
+            require!(
+                node.estimated_footfall >= min,
+                SamizdatError::TargetMismatch
+            );
+        }
+        if let Some(max) = filters.max_footfall {
+            require!(
+                node.estimated_footfall <= max,
+                SamizdatError::TargetMismatch
+            );
+        }
+        if !filters.screen_sizes.is_empty() {
+            require!(
+                filters.screen_sizes.contains(&node.screen_size),
+                SamizdatError::TargetMismatch
+            );
+        }
+        if !filters.geo_regions.is_empty() {
+            require!(
+                filters
+                    .geo_regions
+                    .iter()
+                    .any(|region| region.contains(&node.location)),
+                SamizdatError::TargetMismatch
+            );
+        }
+        if !filters.establishment_types.is_empty() {
+            require!(
+                filters
+                    .establishment_types
+                    .contains(&node.establishment_type),
+                SamizdatError::TargetMismatch
+            );
+        }
+        if let Some(daypart) = &filters.daypart {
+            require!(
+                daypart.is_open_at(unix_timestamp),
+                SamizdatError::OutsideDaypart
+            );
+        }
+        if let Some(min) = filters.min_reputation {
+            require!(node.reputation_bps >= min, SamizdatError::TargetMismatch);
+        }
+        if !filters.required_landmarks.is_empty() {
+            for required in &filters.required_landmarks {
+                require!(
+                    node.landmarks.contains(required),
+                    SamizdatError::TargetMismatch
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Require the campaign's content to pass `node`'s tag policy: no
+    /// blocked tag, and every category the node requires.
+    pub fn check_content_policy(&self, node: &NodeAccount) -> Result<()> {
+        require!(
+            self.tag_mask & node.blocked_tag_mask == 0
+                && self.tag_mask & node.required_tag_mask == node.required_tag_mask,
+            SamizdatError::ContentFilterViolation
+        );
+        Ok(())
+    }
+
+    /// Require `node` to be on the campaign's allowlist, if it has one, and
+    /// off its denylist. Either list can name the NodeAccount or its operator.
+    pub fn check_node_lists(
+        &self,
+        node_key: &Pubkey,
+        node: &NodeAccount,
+        allowlist_proof: Option<&[[u8; 32]]>,
+        denylist: Option<&NodeDenylist>,
+    ) -> Result<()> {
+        let keys = [node_key, &node.authority];
+        if let Some(root) = self.target_filters.node_allowlist {
+            let proof = allowlist_proof.ok_or(SamizdatError::NodeNotAllowlisted)?;
+            require!(
+                keys.iter()
+                    .any(|key| allowlist_root(allowlist_leaf(key), proof) == root),
+                SamizdatError::NodeNotAllowlisted
+            );
+        }
+        if let Some(denylist) = denylist {
+            require!(
+                !keys.iter().any(|key| denylist.contains(key)),
+                SamizdatError::NodeDenylisted
+            );
+        }
+        Ok(())
+    }
+
+    /// Price an open-market play of `creative` on `node` pays. PerSecond
+    /// campaigns count a full play of the creative, since the duration is
+    /// only known at confirmation.
+    pub fn play_price(&self, node: &NodeAccount, creative: &Creative) -> Result<u64> {
+        Ok(match self.pricing {
+            PricingModel::PerPlay => self.bounty_per_play,
+            PricingModel::PerSecond {
+                price_per_second,
+                max_duration_ms,
+            } => PricingModel::per_second_payout(
+                price_per_second,
+                creative.duration_ms.min(max_duration_ms),
+            )?,
+            PricingModel::Cpm { cpm_rate } => {
+                PricingModel::cpm_payout(cpm_rate, node.estimated_footfall, self.bounty_per_play)
+            }
+        })
+    }
+
+    /// Hold `amount` of the vault for a new claim, and a play slot too for
+    /// PerPlay campaigns.
+    pub fn reserve_play(&mut self, vault_balance: u64, amount: u64) -> Result<()> {
+        self.reserve_budget(vault_balance, amount)?;
+        if self.pricing == PricingModel::PerPlay {
+            require!(self.plays_remaining > 0, SamizdatError::NoPlaysRemaining);
+            self.plays_remaining -= 1;
+        }
+        Ok(())
+    }
+
+    /// Release the `reserved` budget held by a confirmed claim. The play
+    /// slot of a PerPlay campaign stays consumed.
+    pub fn settle_play(&mut self, reserved: u64) {
+        self.release_budget(reserved);
+    }
+
+    /// Earmark `amount` of the unreserved vault balance.
+    pub fn reserve_budget(&mut self, vault_balance: u64, amount: u64) -> Result<()> {
+        require!(
+            vault_balance.saturating_sub(self.reserved_budget) >= amount,
+            SamizdatError::InsufficientFunds
+        );
+        self.reserved_budget = self
+            .reserved_budget
+            .checked_add(amount)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn release_budget(&mut self, amount: u64) {
+        self.reserved_budget = self.reserved_budget.saturating_sub(amount);
+    }
+
+    /// Return everything held by a claim that timed out.
+    pub fn cancel_play(&mut self, reserved: u64) -> Result<()> {
+        if self.pricing == PricingModel::PerPlay {
+            self.plays_remaining = self
+                .plays_remaining
+                .checked_add(1)
+                .ok_or(SamizdatError::ArithmeticOverflow)?;
+        }
+        self.settle_play(reserved);
+        Ok(())
+    }
+
+    /// Count a claim against today's pacing budget, rolling the counter
+    /// over when a new UTC day starts.
+    pub fn record_daily_play(&mut self, unix_timestamp: i64) -> Result<()> {
+        let today = unix_timestamp.div_euclid(SECONDS_PER_DAY);
+        if self.pacing_day != today {
+            self.pacing_day = today;
+            self.plays_today = 0;
+        }
+        if let Some(cap) = self.daily_play_cap {
+            require!(self.plays_today < cap, SamizdatError::DailyCapReached);
+        }
+        self.plays_today = self
+            .plays_today
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Return a claim made at `claimed_at` to today's pacing budget.
+    /// Claims from earlier days were already rolled over.
+    pub fn release_daily_play(&mut self, claimed_at: i64) {
+        if claimed_at.div_euclid(SECONDS_PER_DAY) == self.pacing_day {
+            self.plays_today = self.plays_today.saturating_sub(1);
+        }
+    }
+
+    /// Require the flight window, when fully specified, to end after it starts.
+    pub fn validate_flight_window(start_at: Option<i64>, end_at: Option<i64>) -> Result<()> {
+        if let (Some(start), Some(end)) = (start_at, end_at) {
+            require!(start < end, SamizdatError::InvalidFlightWindow);
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use super::shared::{MAX_NONCE_ADVANCE, NONCE_WINDOW_SIZE};
+use crate::errors::SamizdatError;
 use anchor_lang::prelude::*;
 
 /// Tracks the last time a specific node claimed a specific campaign.
@@ -8,5 +10,86 @@ pub struct ClaimCooldown {
     pub campaign: Pubkey,
     pub node: Pubkey,
     pub last_claimed_at: i64,
+    /// Highest claim nonce seen for this (campaign, node) pair
+    pub nonce_high: i64,
+    /// Bit i set = nonce `nonce_high - i` has been used
+    pub nonce_window: u64,
     pub bump: u8,
 }
+
+impl ClaimCooldown {
+    /// Mark `nonce` as used. Rejects nonces already in the window and nonces
+    /// too old to be tracked, so a PlayRecord PDA can never be recreated
+    /// after it has been closed.
+    ///
+    /// Node signers must use roughly increasing nonces: after the first
+    /// claim, a nonce may be at most `MAX_NONCE_ADVANCE` above the highest
+    /// one used, and no more than `NONCE_WINDOW_SIZE - 1` below it.
+    pub fn record_nonce(&mut self, nonce: i64) -> Result<()> {
+        if nonce > self.nonce_high {
+            let shift = nonce.abs_diff(self.nonce_high);
+            require!(
+                self.nonce_window == 0 || shift <= MAX_NONCE_ADVANCE,
+                SamizdatError::NonceTooFarAhead
+            );
+            self.nonce_window = if shift >= NONCE_WINDOW_SIZE as u64 {
+                0
+            } else {
+                self.nonce_window << shift
+            };
+            self.nonce_window |= 1;
+            self.nonce_high = nonce;
+            return Ok(());
+        }
+
+        let age = nonce.abs_diff(self.nonce_high);
+        require!(
+            age < NONCE_WINDOW_SIZE as u64,
+            SamizdatError::NonceAlreadyUsed
+        );
+        let bit = 1u64 << age;
+        require!(
+            self.nonce_window & bit == 0,
+            SamizdatError::NonceAlreadyUsed
+        );
+        self.nonce_window |= bit;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cooldown() -> ClaimCooldown {
+        ClaimCooldown {
+            campaign: Pubkey::default(),
+            node: Pubkey::default(),
+            last_claimed_at: 0,
+            nonce_high: 0,
+            nonce_window: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn first_nonce_may_start_anywhere() {
+        let mut cooldown = cooldown();
+        assert!(cooldown.record_nonce(1_000_000).is_ok());
+        assert!(cooldown.record_nonce(1_000_000).is_err());
+        assert!(cooldown.record_nonce(999_999).is_ok());
+    }
+
+    #[test]
+    fn nonce_cannot_jump_past_the_window() {
+        let mut cooldown = cooldown();
+        assert!(cooldown.record_nonce(1).is_ok());
+        assert!(cooldown
+            .record_nonce(1 + MAX_NONCE_ADVANCE as i64 + 1)
+            .is_err());
+        assert!(cooldown.record_nonce(1 + MAX_NONCE_ADVANCE as i64).is_ok());
+        // Nonces skipped by the largest allowed step stay claimable
+        assert!(cooldown.record_nonce(2).is_ok());
+        assert!(cooldown.record_nonce(2).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use super::publisher_account::PublisherAccount;
+use super::shared::MAX_FREQUENCY_CAP_PLAYS;
+use crate::errors::SamizdatError;
+use anchor_lang::prelude::*;
+
+/// Recent claims of one publisher's campaigns on one node, across all of
+/// them, for the publisher's node cooldown and frequency cap.
+/// Seeded by [FREQUENCY_SEED, publisher.key(), node.key()].
+#[account]
+#[derive(InitSpace)]
+pub struct FrequencyTracker {
+    pub publisher_account: Pubkey,
+    pub node_account: Pubkey,
+    pub last_claimed_at: i64,
+    /// Claim timestamps inside the frequency cap window, oldest first
+    #[max_len(MAX_FREQUENCY_CAP_PLAYS)]
+    pub recent_claims: Vec<i64>,
+    pub bump: u8,
+}
+
+impl FrequencyTracker {
+    /// Require a claim at `unix_timestamp` to respect the publisher's node
+    /// cooldown and frequency cap, then count it. The cap is a sliding
+    /// window over the last `window_seconds`.
+    pub fn record_claim(
+        &mut self,
+        publisher: &PublisherAccount,
+        unix_timestamp: i64,
+    ) -> Result<()> {
+        if self.last_claimed_at > 0 {
+            require!(
+                unix_timestamp >= self.last_claimed_at.saturating_add(publisher.node_cooldown),
+                SamizdatError::CooldownNotExpired
+            );
+        }
+        self.last_claimed_at = unix_timestamp;
+
+        match publisher.frequency_cap {
+            Some(cap) => {
+                self.recent_claims
+                    .retain(|&claimed_at| claimed_at > unix_timestamp - cap.window_seconds);
+                require!(
+                    self.recent_claims.len() < cap.max_plays as usize,
+                    SamizdatError::FrequencyCapReached
+                );
+                self.recent_claims.push(unix_timestamp);
+            }
+            // Nothing to count against; a cap set later starts empty
+            None => self.recent_claims.clear(),
+        }
+        Ok(())
+    }
+
+    /// Stop counting a claim made at `claimed_at` that was never played.
+    pub fn release_claim(&mut self, claimed_at: i64) {
+        if let Some(index) = self.recent_claims.iter().position(|&at| at == claimed_at) {
+            self.recent_claims.remove(index);
+        }
+    }
+}
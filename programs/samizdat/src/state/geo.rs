@@ -0,0 +1,626 @@
+use super::shared::{
+    GeoLocation, EARTH_RADIUS_M, GEOHASH_PRECISION, GEO_DEGREE, MAX_CAMPAIGN_GEO_CELLS,
+    MAX_GEO_RADIUS_M, MAX_POLYGON_VERTICES,
+};
+use crate::errors::SamizdatError;
+use anchor_lang::prelude::*;
+
+const MAX_LATITUDE: i64 = 90 * GEO_DEGREE;
+const MAX_LONGITUDE: i64 = 180 * GEO_DEGREE;
+const FULL_TURN: i64 = 360 * GEO_DEGREE;
+
+/// Scale of the fixed-point sines and unit vectors (1.0 = 1e9).
+const UNIT: i64 = 1_000_000_000;
+
+/// Length of one degree of latitude, rounded down so bounding boxes err wide.
+const METRES_PER_DEGREE: i64 = 111_194;
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// sin(d°) × 1e9 for d = 0..=90, interpolated linearly in between.
+#[rustfmt::skip]
+const SIN_DEGREES: [i64; 91] = [
+    0, 17_452_406, 34_899_497, 52_335_956, 69_756_474, 87_155_743,
+    104_528_463, 121_869_343, 139_173_101, 156_434_465, 173_648_178, 190_808_995,
+    207_911_691, 224_951_054, 241_921_896, 258_819_045, 275_637_356, 292_371_705,
+    309_016_994, 325_568_154, 342_020_143, 358_367_950, 374_606_593, 390_731_128,
+    406_736_643, 422_618_262, 438_371_147, 453_990_500, 469_471_563, 484_809_620,
+    500_000_000, 515_038_075, 529_919_264, 544_639_035, 559_192_903, 573_576_436,
+    587_785_252, 601_815_023, 615_661_475, 629_320_391, 642_787_610, 656_059_029,
+    669_130_606, 681_998_360, 694_658_370, 707_106_781, 719_339_800, 731_353_702,
+    743_144_825, 754_709_580, 766_044_443, 777_145_961, 788_010_754, 798_635_510,
+    809_016_994, 819_152_044, 829_037_573, 838_670_568, 848_048_096, 857_167_301,
+    866_025_404, 874_619_707, 882_947_593, 891_006_524, 898_794_046, 906_307_787,
+    913_545_458, 920_504_853, 927_183_855, 933_580_426, 939_692_621, 945_518_576,
+    951_056_516, 956_304_756, 961_261_696, 965_925_826, 970_295_726, 974_370_065,
+    978_147_601, 981_627_183, 984_807_753, 987_688_341, 990_268_069, 992_546_152,
+    994_521_895, 996_194_698, 997_564_050, 998_629_535, 999_390_827, 999_847_695,
+    1_000_000_000,
+];
+
+/// Area a campaign targets. A node matches when its location is inside.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum GeoRegion {
+    /// Latitude/longitude box
+    Rect { bounds: GeoBounds },
+    /// Everything within `radius_m` metres of `center` on the surface
+    Circle { center: GeoLocation, radius_m: u32 },
+    /// Simple polygon whose edges are straight lines in latitude/longitude.
+    /// It may cross the antimeridian but must not contain a pole.
+    Polygon {
+        #[max_len(MAX_POLYGON_VERTICES)]
+        vertices: Vec<GeoLocation>,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct GeoBounds {
+    // x 1e7 fixed-points
+    pub min_lat: i64,
+    pub max_lat: i64,
+    /// Greater than `max_lon` when the box crosses the antimeridian
+    pub min_lon: i64,
+    pub max_lon: i64,
+}
+
+impl GeoRegion {
+    pub fn validate(&self) -> Result<()> {
+        let valid = match self {
+            Self::Rect { bounds } => {
+                is_valid_location(bounds.min_lat, bounds.min_lon)
+                    && is_valid_location(bounds.max_lat, bounds.max_lon)
+                    && bounds.min_lat <= bounds.max_lat
+            }
+            Self::Circle { center, radius_m } => {
+                is_valid_location(center.latitude, center.longitude)
+                    && *radius_m > 0
+                    && *radius_m <= MAX_GEO_RADIUS_M
+            }
+            Self::Polygon { vertices } => {
+                (3..=MAX_POLYGON_VERTICES).contains(&vertices.len())
+                    && vertices
+                        .iter()
+                        .all(|v| is_valid_location(v.latitude, v.longitude))
+                    && {
+                        // Less than half the globe wide, so relative longitudes
+                        // are unambiguous
+                        let reference = vertices[0].longitude;
+                        let (min, max) = vertices
+                            .iter()
+                            .map(|v| relative_longitude(v.longitude, reference))
+                            .fold((0, 0), |(min, max), x| (x.min(min), x.max(max)));
+                        max - min < MAX_LONGITUDE
+                    }
+            }
+        };
+        require!(valid, SamizdatError::InvalidGeoRegion);
+        Ok(())
+    }
+
+    pub fn contains(&self, location: &GeoLocation) -> bool {
+        match self {
+            Self::Rect { bounds } => bounds.contains(location),
+            Self::Circle { center, radius_m } => {
+                // For the radii allowed the chord between the two points is
+                // within 0.1% of the surface distance
+                let chord_squared = chord_squared(center, location);
+                let radius = *radius_m as i128 * UNIT as i128;
+                chord_squared * (EARTH_RADIUS_M as i128).pow(2) <= radius * radius
+            }
+            Self::Polygon { vertices } => polygon_contains(vertices, location),
+        }
+    }
+}
+
+impl GeoRegion {
+    /// Boxes covering the region, split at the antimeridian so each has
+    /// `min_lon <= max_lon`.
+    fn bounding_boxes(&self) -> Vec<GeoBounds> {
+        let (min_lat, max_lat, west, east) = match self {
+            Self::Rect { bounds } if bounds.min_lon > bounds.max_lon => (
+                bounds.min_lat,
+                bounds.max_lat,
+                bounds.min_lon,
+                bounds.max_lon + FULL_TURN,
+            ),
+            Self::Rect { bounds } => (
+                bounds.min_lat,
+                bounds.max_lat,
+                bounds.min_lon,
+                bounds.max_lon,
+            ),
+            Self::Circle { center, radius_m } => {
+                // Padded by 1% to cover the tolerance of the distance test
+                let dlat = *radius_m as i64 * GEO_DEGREE * 101 / (METRES_PER_DEGREE * 100) + 1;
+                let min_lat = (center.latitude - dlat).max(-MAX_LATITUDE);
+                let max_lat = (center.latitude + dlat).min(MAX_LATITUDE);
+                // Parallels shrink towards the poles, so widen by the
+                // latitude furthest from the equator
+                let cos_far = cos(min_lat.abs().max(max_lat.abs()));
+                if cos_far <= 0 || dlat * UNIT / cos_far >= MAX_LONGITUDE {
+                    (min_lat, max_lat, -MAX_LONGITUDE, MAX_LONGITUDE)
+                } else {
+                    let dlon = dlat * UNIT / cos_far + 1;
+                    (
+                        min_lat,
+                        max_lat,
+                        center.longitude - dlon,
+                        center.longitude + dlon,
+                    )
+                }
+            }
+            Self::Polygon { vertices } => {
+                let reference = vertices[0].longitude;
+                let (min_lat, max_lat, west, east) = vertices.iter().fold(
+                    (MAX_LATITUDE, -MAX_LATITUDE, 0, 0),
+                    |(min_lat, max_lat, west, east), v| {
+                        let x = relative_longitude(v.longitude, reference);
+                        (
+                            min_lat.min(v.latitude),
+                            max_lat.max(v.latitude),
+                            west.min(x),
+                            east.max(x),
+                        )
+                    },
+                );
+                (min_lat, max_lat, reference + west, reference + east)
+            }
+        };
+
+        let bounds = |min_lon, max_lon| GeoBounds {
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+        };
+        if east - west >= FULL_TURN {
+            vec![bounds(-MAX_LONGITUDE, MAX_LONGITUDE)]
+        } else if west < -MAX_LONGITUDE {
+            vec![
+                bounds(west + FULL_TURN, MAX_LONGITUDE),
+                bounds(-MAX_LONGITUDE, east),
+            ]
+        } else if east > MAX_LONGITUDE {
+            vec![
+                bounds(west, MAX_LONGITUDE),
+                bounds(-MAX_LONGITUDE, east - FULL_TURN),
+            ]
+        } else {
+            vec![bounds(west, east)]
+        }
+    }
+}
+
+/// Sorted geohash cells covering every region, at the finest precision up to
+/// `GEOHASH_PRECISION` that needs at most `MAX_CAMPAIGN_GEO_CELLS` of them.
+/// Regions too wide for that go in the empty cell, which covers the globe.
+pub fn geo_cells(regions: &[GeoRegion]) -> Vec<String> {
+    if regions.is_empty() {
+        return Vec::new();
+    }
+    let boxes: Vec<GeoBounds> = regions.iter().flat_map(GeoRegion::bounding_boxes).collect();
+    (0..=GEOHASH_PRECISION)
+        .rev()
+        .find_map(|precision| cover(&boxes, precision))
+        .unwrap_or_default()
+}
+
+/// Geohash of the cell containing `location`.
+pub fn geohash(location: &GeoLocation, precision: usize) -> String {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    encode_cell(
+        cell_index(location.longitude, MAX_LONGITUDE, lon_bits),
+        cell_index(location.latitude, MAX_LATITUDE, lat_bits),
+        precision,
+    )
+}
+
+/// Longitude and latitude bits in a geohash of `precision` characters.
+fn geohash_bits(precision: usize) -> (u32, u32) {
+    let bits = 5 * precision as u32;
+    (bits.div_ceil(2), bits / 2)
+}
+
+/// Column or row of `coordinate` in a grid of 2^bits cells over
+/// `-half_range..=half_range`.
+fn cell_index(coordinate: i64, half_range: i64, bits: u32) -> u64 {
+    let index = (coordinate + half_range) as u128 * (1u128 << bits) / (2 * half_range) as u128;
+    index.min((1u128 << bits) - 1) as u64
+}
+
+fn encode_cell(column: u64, row: u64, precision: usize) -> String {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    let mut hash = String::with_capacity(precision);
+    let mut char_index = 0;
+    for bit in 0..5 * precision as u32 {
+        // Bits alternate longitude, latitude, starting with longitude
+        let value = if bit % 2 == 0 {
+            column >> (lon_bits - 1 - bit / 2) & 1
+        } else {
+            row >> (lat_bits - 1 - bit / 2) & 1
+        };
+        char_index = char_index << 1 | value as usize;
+        if bit % 5 == 4 {
+            hash.push(GEOHASH_ALPHABET[char_index] as char);
+            char_index = 0;
+        }
+    }
+    hash
+}
+
+/// Cells of `precision` covering `boxes`, or None if there are more than
+/// `MAX_CAMPAIGN_GEO_CELLS`.
+fn cover(boxes: &[GeoBounds], precision: usize) -> Option<Vec<String>> {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    let mut cells = Vec::new();
+    for bounds in boxes {
+        let west = cell_index(bounds.min_lon, MAX_LONGITUDE, lon_bits);
+        let east = cell_index(bounds.max_lon, MAX_LONGITUDE, lon_bits);
+        let south = cell_index(bounds.min_lat, MAX_LATITUDE, lat_bits);
+        let north = cell_index(bounds.max_lat, MAX_LATITUDE, lat_bits);
+        if (east - west + 1) * (north - south + 1) > MAX_CAMPAIGN_GEO_CELLS as u64 {
+            return None;
+        }
+        for column in west..=east {
+            for row in south..=north {
+                cells.push(encode_cell(column, row, precision));
+            }
+        }
+    }
+    cells.sort();
+    cells.dedup();
+    (cells.len() <= MAX_CAMPAIGN_GEO_CELLS).then_some(cells)
+}
+
+impl GeoBounds {
+    pub fn contains(&self, location: &GeoLocation) -> bool {
+        let lat_ok = location.latitude >= self.min_lat && location.latitude <= self.max_lat;
+        let lon_ok = if self.min_lon <= self.max_lon {
+            location.longitude >= self.min_lon && location.longitude <= self.max_lon
+        } else {
+            location.longitude >= self.min_lon || location.longitude <= self.max_lon
+        };
+        lat_ok && lon_ok
+    }
+}
+
+fn is_valid_location(latitude: i64, longitude: i64) -> bool {
+    (-MAX_LATITUDE..=MAX_LATITUDE).contains(&latitude)
+        && (-MAX_LONGITUDE..=MAX_LONGITUDE).contains(&longitude)
+}
+
+/// `longitude` relative to `reference`, wrapped into [-180°, 180°).
+fn relative_longitude(longitude: i64, reference: i64) -> i64 {
+    (longitude - reference + MAX_LONGITUDE).rem_euclid(FULL_TURN) - MAX_LONGITUDE
+}
+
+/// sin of an angle in degrees × 1e7, scaled by `UNIT`.
+fn sin(angle: i64) -> i64 {
+    let half_turn = FULL_TURN / 2;
+    let quarter_turn = FULL_TURN / 4;
+    let angle = angle.rem_euclid(FULL_TURN);
+    let (angle, sign) = if angle >= half_turn {
+        (angle - half_turn, -1)
+    } else {
+        (angle, 1)
+    };
+    let angle = if angle > quarter_turn {
+        half_turn - angle
+    } else {
+        angle
+    };
+    let index = (angle / GEO_DEGREE) as usize;
+    let low = SIN_DEGREES[index];
+    let high = SIN_DEGREES[(index + 1).min(90)];
+    sign * (low + (high - low) * (angle % GEO_DEGREE) / GEO_DEGREE)
+}
+
+fn cos(angle: i64) -> i64 {
+    sin(angle + FULL_TURN / 4)
+}
+
+/// Point on the unit sphere, each axis scaled by `UNIT`.
+fn unit_vector(location: &GeoLocation) -> [i64; 3] {
+    let cos_lat = cos(location.latitude);
+    [
+        cos_lat * cos(location.longitude) / UNIT,
+        cos_lat * sin(location.longitude) / UNIT,
+        sin(location.latitude),
+    ]
+}
+
+/// Squared straight-line distance between two points on the unit sphere,
+/// scaled by `UNIT²`.
+fn chord_squared(a: &GeoLocation, b: &GeoLocation) -> i128 {
+    let (a, b) = (unit_vector(a), unit_vector(b));
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| ((a - b) as i128).pow(2))
+        .sum()
+}
+
+/// Crossing-number test with longitudes taken relative to the first vertex.
+fn polygon_contains(vertices: &[GeoLocation], location: &GeoLocation) -> bool {
+    let reference = vertices[0].longitude;
+    let x = relative_longitude(location.longitude, reference) as i128;
+    let y = location.latitude as i128;
+    let mut inside = false;
+    let mut previous = &vertices[vertices.len() - 1];
+    for current in vertices {
+        let (x1, y1) = (
+            relative_longitude(previous.longitude, reference) as i128,
+            previous.latitude as i128,
+        );
+        let (x2, y2) = (
+            relative_longitude(current.longitude, reference) as i128,
+            current.latitude as i128,
+        );
+        if (y1 > y) != (y2 > y) {
+            // Whether the point lies left of the edge where it crosses y,
+            // without dividing by y2 - y1
+            let lhs = (x - x1) * (y2 - y1);
+            let rhs = (y - y1) * (x2 - x1);
+            if (y2 > y1 && lhs < rhs) || (y2 < y1 && lhs > rhs) {
+                inside = !inside;
+            }
+        }
+        previous = current;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(latitude: f64, longitude: f64) -> GeoLocation {
+        GeoLocation {
+            latitude: (latitude * GEO_DEGREE as f64).round() as i64,
+            longitude: (longitude * GEO_DEGREE as f64).round() as i64,
+        }
+    }
+
+    fn circle(latitude: f64, longitude: f64, radius_m: u32) -> GeoRegion {
+        GeoRegion::Circle {
+            center: at(latitude, longitude),
+            radius_m,
+        }
+    }
+
+    fn rect(min_lat: f64, max_lat: f64, min_lon: f64, max_lon: f64) -> GeoRegion {
+        let corner = at(min_lat, min_lon);
+        let opposite = at(max_lat, max_lon);
+        GeoRegion::Rect {
+            bounds: GeoBounds {
+                min_lat: corner.latitude,
+                max_lat: opposite.latitude,
+                min_lon: corner.longitude,
+                max_lon: opposite.longitude,
+            },
+        }
+    }
+
+    fn polygon(points: &[(f64, f64)]) -> GeoRegion {
+        GeoRegion::Polygon {
+            vertices: points.iter().map(|&(lat, lon)| at(lat, lon)).collect(),
+        }
+    }
+
+    /// Great-circle distance in metres.
+    fn haversine(a: (f64, f64), b: (f64, f64)) -> f64 {
+        let (lat1, lat2) = (a.0.to_radians(), b.0.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (b.1 - a.1).to_radians();
+        let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_M as f64 * h.sqrt().asin()
+    }
+
+    #[test]
+    fn sin_matches_float_within_interpolation_error() {
+        for tenth in -3600..=3600 {
+            let degrees = tenth as f64 / 10.0;
+            let expected = degrees.to_radians().sin();
+            let actual = sin((degrees * GEO_DEGREE as f64) as i64) as f64 / UNIT as f64;
+            assert!((expected - actual).abs() < 4e-5, "sin({degrees})");
+        }
+    }
+
+    #[test]
+    fn circle_matches_great_circle_distance() {
+        let centers = [
+            (40.7128, -74.006),
+            (0.0, 0.0),
+            (-33.86, 151.21),
+            (64.1, -21.9),
+        ];
+        for (lat, lon) in centers {
+            let region = circle(lat, lon, 2_000);
+            for bearing in 0..16 {
+                let angle = (bearing as f64 * 22.5).to_radians();
+                for (metres, inside) in [(1_950.0, true), (2_050.0, false)] {
+                    let dlat = metres * angle.cos() / 111_195.0;
+                    let dlon = metres * angle.sin() / (111_195.0 * lat.to_radians().cos());
+                    let point = (lat + dlat, lon + dlon);
+                    let distance = haversine((lat, lon), point);
+                    assert_eq!(
+                        region.contains(&at(point.0, point.1)),
+                        inside,
+                        "{distance:.0} m from ({lat}, {lon})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn circle_crosses_the_antimeridian() {
+        let region = circle(-16.5, 179.99, 5_000);
+        assert!(region.contains(&at(-16.5, -179.99)));
+        assert!(region.contains(&at(-16.5, 180.0)));
+        assert!(!region.contains(&at(-16.5, -179.9)));
+        assert!(!region.contains(&at(-16.5, 0.0)));
+    }
+
+    #[test]
+    fn circle_covers_the_poles() {
+        // Any longitude at the pole is the same point
+        let north = circle(90.0, 0.0, 1_000);
+        assert!(north.contains(&at(90.0, 123.0)));
+        assert!(north.contains(&at(89.995, -90.0)));
+        assert!(!north.contains(&at(89.98, 45.0)));
+
+        // Across the pole, 89.99° on opposite meridians is ~2.2 km apart
+        let near_south = circle(-89.99, 0.0, 2_500);
+        assert!(near_south.contains(&at(-89.99, 180.0)));
+        assert!(!circle(-89.99, 0.0, 2_000).contains(&at(-89.99, 180.0)));
+    }
+
+    #[test]
+    fn rect_wraps_across_the_antimeridian() {
+        let region = rect(-20.0, -10.0, 170.0, -170.0);
+        assert!(region.validate().is_ok());
+        assert!(region.contains(&at(-15.0, 175.0)));
+        assert!(region.contains(&at(-15.0, -175.0)));
+        assert!(region.contains(&at(-15.0, 180.0)));
+        assert!(!region.contains(&at(-15.0, 0.0)));
+        assert!(!region.contains(&at(-25.0, 175.0)));
+    }
+
+    #[test]
+    fn rect_reaches_the_pole() {
+        let region = rect(80.0, 90.0, -180.0, 180.0);
+        assert!(region.contains(&at(90.0, 0.0)));
+        assert!(region.contains(&at(85.0, -179.0)));
+        assert!(!region.contains(&at(79.0, 0.0)));
+    }
+
+    #[test]
+    fn polygon_contains_points_inside_only() {
+        // Concave "L" shape
+        let region = polygon(&[
+            (0.0, 0.0),
+            (0.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 0.0),
+        ]);
+        assert!(region.validate().is_ok());
+        assert!(region.contains(&at(0.5, 1.5)));
+        assert!(region.contains(&at(1.5, 0.5)));
+        assert!(!region.contains(&at(1.5, 1.5)));
+        assert!(!region.contains(&at(-0.5, 0.5)));
+    }
+
+    #[test]
+    fn polygon_crosses_the_antimeridian() {
+        let region = polygon(&[
+            (-17.0, 178.0),
+            (-17.0, -178.0),
+            (-15.0, -178.0),
+            (-15.0, 178.0),
+        ]);
+        assert!(region.validate().is_ok());
+        assert!(region.contains(&at(-16.0, 179.5)));
+        assert!(region.contains(&at(-16.0, -179.5)));
+        assert!(region.contains(&at(-16.0, 180.0)));
+        assert!(!region.contains(&at(-16.0, 177.0)));
+        assert!(!region.contains(&at(-16.0, 0.0)));
+    }
+
+    #[test]
+    fn polygon_near_the_pole() {
+        let region = polygon(&[(85.0, -10.0), (85.0, 10.0), (89.9, 10.0), (89.9, -10.0)]);
+        assert!(region.validate().is_ok());
+        assert!(region.contains(&at(89.5, 0.0)));
+        assert!(!region.contains(&at(89.5, 20.0)));
+        assert!(!region.contains(&at(90.0, 0.0)));
+    }
+
+    #[test]
+    fn rejects_malformed_regions() {
+        assert!(rect(10.0, 0.0, 0.0, 1.0).validate().is_err());
+        assert!(rect(0.0, 91.0, 0.0, 1.0).validate().is_err());
+        assert!(rect(0.0, 1.0, 0.0, 181.0).validate().is_err());
+        assert!(circle(0.0, 0.0, 0).validate().is_err());
+        assert!(circle(0.0, 0.0, MAX_GEO_RADIUS_M + 1).validate().is_err());
+        assert!(circle(-90.5, 0.0, 100).validate().is_err());
+        assert!(polygon(&[(0.0, 0.0), (1.0, 1.0)]).validate().is_err());
+        assert!(polygon(&[(0.0, -100.0), (0.0, 100.0), (10.0, 0.0)])
+            .validate()
+            .is_err());
+        assert!(polygon(&[(0.0, 0.0); MAX_POLYGON_VERTICES + 1])
+            .validate()
+            .is_err());
+    }
+
+    /// Every sampled point a region contains falls in one of its cells.
+    fn assert_cells_cover(region: GeoRegion) {
+        let cells = geo_cells(std::slice::from_ref(&region));
+        assert!(!cells.is_empty() && cells.len() <= MAX_CAMPAIGN_GEO_CELLS);
+        let precision = cells[0].len();
+        for lat in (-900..=900).step_by(2) {
+            for lon in (-1800..=1800).step_by(2) {
+                let point = at(lat as f64 / 10.0, lon as f64 / 10.0);
+                if region.contains(&point) {
+                    let cell = geohash(&point, precision);
+                    assert!(cells.contains(&cell), "{cell} at ({lat}, {lon}) / 10");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn geohash_matches_reference_encoder() {
+        assert_eq!(geohash(&at(57.64911, 10.40744), 4), "u4pr");
+        assert_eq!(geohash(&at(40.7128, -74.006), 4), "dr5r");
+        assert_eq!(geohash(&at(-33.86, 151.21), 2), "r3");
+        assert_eq!(geohash(&at(90.0, 180.0), 1), "z");
+        assert_eq!(geohash(&at(-90.0, -180.0), 1), "0");
+    }
+
+    #[test]
+    fn small_circle_indexes_its_own_cell() {
+        let cells = geo_cells(&[circle(40.7128, -74.006, 2_000)]);
+        assert_eq!(cells, ["dr5r"]);
+    }
+
+    #[test]
+    fn cells_cover_every_contained_point() {
+        assert_cells_cover(circle(40.7128, -74.006, 150_000));
+        assert_cells_cover(circle(-16.5, 179.9, 100_000));
+        assert_cells_cover(circle(89.5, 0.0, 200_000));
+        assert_cells_cover(rect(-20.0, -10.0, 170.0, -170.0));
+        assert_cells_cover(polygon(&[
+            (-17.0, 178.0),
+            (-17.0, -178.0),
+            (-15.0, -178.0),
+            (-15.0, 178.0),
+        ]));
+        assert_cells_cover(polygon(&[
+            (85.0, -10.0),
+            (85.0, 10.0),
+            (89.9, 10.0),
+            (89.9, -10.0),
+        ]));
+    }
+
+    #[test]
+    fn cells_coarsen_to_fit_the_limit() {
+        let cells = geo_cells(&[rect(10.0, 40.0, -100.0, -10.0)]);
+        assert!(cells.iter().all(|cell| cell.len() == 1));
+        assert!(geo_cells(&[]).is_empty());
+    }
+
+    #[test]
+    fn regions_too_wide_for_any_cell_go_global() {
+        assert_eq!(geo_cells(&[rect(-90.0, 90.0, -180.0, 180.0)]), [""]);
+        assert_eq!(
+            geo_cells(&[
+                rect(-60.0, 60.0, -170.0, -10.0),
+                rect(-60.0, 60.0, 10.0, 170.0),
+            ]),
+            [""]
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use super::shared::GEOHASH_PRECISION;
+use anchor_lang::prelude::*;
+
+/// Campaigns whose geo regions overlap one geohash cell, so a node can find
+/// the campaigns near it by fetching the cells its location falls in.
+/// Seeded by [GEO_INDEX_SEED, cell]. The account grows and shrinks with
+/// `campaigns`, up to MAX_GEO_INDEX_CAMPAIGNS entries.
+#[account]
+pub struct GeoIndex {
+    /// Geohash of 1..=GEOHASH_PRECISION characters
+    pub cell: String,
+    pub campaigns: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl GeoIndex {
+    /// Account size for an index listing `campaigns` campaigns.
+    pub fn space(campaigns: usize) -> usize {
+        8 + (4 + GEOHASH_PRECISION) + (4 + campaigns * 32) + 1
+    }
+}
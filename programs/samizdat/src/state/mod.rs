@@ -1,13 +1,31 @@
+pub mod approved_campaign;
+pub mod bid;
+pub mod booking;
 pub mod campaign_account;
 pub mod claim_cooldown;
+pub mod frequency_tracker;
+pub mod geo;
+pub mod geo_index;
 pub mod node_account;
+pub mod node_lists;
 pub mod play_record;
+pub mod protocol_config;
 pub mod publisher_account;
 pub mod shared;
+pub mod slot_auction;
 
+pub use approved_campaign::*;
+pub use bid::*;
+pub use booking::*;
 pub use campaign_account::*;
 pub use claim_cooldown::*;
+pub use frequency_tracker::*;
+pub use geo::*;
+pub use geo_index::*;
 pub use node_account::*;
+pub use node_lists::*;
 pub use play_record::*;
+pub use protocol_config::*;
 pub use publisher_account::*;
 pub use shared::*;
+pub use slot_auction::*;
@@ -1,7 +1,9 @@
 use super::shared::{
-    GeoLocation, NodeStatus, Resolution, ScreenSize, MAX_ESTABLISHMENT_TYPE_LENGTH, MAX_LANDMARKS,
-    MAX_LANDMARK_LENGTH,
+    AuctionedSlot, BookedWindow, GeoLocation, NodeStatus, Resolution, ScreenSize, BPS_DENOMINATOR,
+    MAX_ESTABLISHMENT_TYPE_LENGTH, MAX_LANDMARKS, MAX_LANDMARK_LENGTH, MAX_NODE_AUCTIONED_SLOTS,
+    MAX_NODE_BOOKINGS, REPUTATION_ALPHA_BPS,
 };
+use crate::errors::SamizdatError;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,18 +11,200 @@ use anchor_lang::prelude::*;
 pub struct NodeAccount {
     pub authority: Pubkey,
     pub node_id: u64,
+    /// Ed25519 key held by the renderer, used to sign proof-of-play payloads
+    pub signing_key: Pubkey,
     pub location: GeoLocation,
     pub screen_size: ScreenSize,
     pub resolution: Resolution,
     #[max_len(MAX_LANDMARKS, MAX_LANDMARK_LENGTH)]
     pub landmarks: Vec<String>,
     pub blocked_tag_mask: u64,
+    /// Content categories a campaign must carry all of to play here
+    pub required_tag_mask: u64,
+    /// Lowest price per play the node accepts
+    pub min_bounty_per_play: u64,
+    /// Only creatives the operator has approved with an ApprovedCampaign
+    /// can be claimed
+    pub requires_approval: bool,
     pub estimated_footfall: u32,
     #[max_len(MAX_ESTABLISHMENT_TYPE_LENGTH)]
     pub establishment_type: String,
+    /// Confirmed plays
     pub total_plays: u64,
+    /// Plays claimed, whatever their outcome
+    pub total_claims: u64,
+    /// Claims that expired without confirmation
+    pub timed_out_plays: u64,
+    /// Disputes resolved against this node by the protocol admin
+    pub disputes_lost: u64,
+    /// Exponentially-decayed score of play outcomes, 0..=10000
+    pub reputation_bps: u16,
     pub total_earnings: u64,
+    /// Claim bonds lost to timeout_play
+    pub bonds_forfeited: u64,
     pub registered_at: i64,
     pub status: NodeStatus,
+    /// Admin-assigned reason code while Suspended, 0 otherwise
+    pub suspension_reason: u16,
+    /// Accepted bookings that have not been settled yet
+    #[max_len(MAX_NODE_BOOKINGS)]
+    pub bookings: Vec<BookedWindow>,
+    /// Slots of open or won auctions that have not been claimed yet
+    #[max_len(MAX_NODE_AUCTIONED_SLOTS)]
+    pub auctioned_slots: Vec<AuctionedSlot>,
     pub bump: u8,
 }
+
+impl NodeAccount {
+    /// The accepted booking whose window covers `unix_timestamp`, if any.
+    pub fn booking_at(&self, unix_timestamp: i64) -> Option<&BookedWindow> {
+        self.bookings.iter().find(|w| w.contains(unix_timestamp))
+    }
+
+    /// Reserve a window for an accepted booking. Windows may not overlap
+    /// other bookings or auctioned slots.
+    pub fn add_booking(&mut self, window: BookedWindow) -> Result<()> {
+        require!(
+            self.bookings.len() < MAX_NODE_BOOKINGS,
+            SamizdatError::TooManyBookings
+        );
+        require!(
+            !self.is_reserved(window.start_at, window.end_at),
+            SamizdatError::BookingOverlap
+        );
+        self.bookings.push(window);
+        Ok(())
+    }
+
+    pub fn remove_booking(&mut self, booking: &Pubkey) {
+        self.bookings.retain(|w| w.booking != *booking);
+    }
+
+    /// The auctioned slot covering `unix_timestamp`, if any.
+    pub fn auctioned_slot_at(&self, unix_timestamp: i64) -> Option<&AuctionedSlot> {
+        self.auctioned_slots
+            .iter()
+            .find(|s| s.contains(unix_timestamp))
+    }
+
+    /// Set a slot aside for an auction, dropping slots that have already
+    /// ended. Slots may not overlap bookings or other auctioned slots.
+    pub fn add_auctioned_slot(&mut self, slot: AuctionedSlot, unix_timestamp: i64) -> Result<()> {
+        self.auctioned_slots.retain(|s| s.slot_end > unix_timestamp);
+        require!(
+            self.auctioned_slots.len() < MAX_NODE_AUCTIONED_SLOTS,
+            SamizdatError::TooManyAuctionedSlots
+        );
+        require!(
+            !self.is_reserved(slot.slot_start, slot.slot_end),
+            SamizdatError::SlotOverlap
+        );
+        self.auctioned_slots.push(slot);
+        Ok(())
+    }
+
+    pub fn remove_auctioned_slot(&mut self, slot_auction: &Pubkey) {
+        self.auctioned_slots
+            .retain(|s| s.slot_auction != *slot_auction);
+    }
+
+    /// Whether a booking or auctioned slot overlaps `[start_at, end_at)`.
+    fn is_reserved(&self, start_at: i64, end_at: i64) -> bool {
+        self.bookings.iter().any(|w| w.overlaps(start_at, end_at))
+            || self
+                .auctioned_slots
+                .iter()
+                .any(|s| s.overlaps(start_at, end_at))
+    }
+
+    pub fn record_claim(&mut self) -> Result<()> {
+        self.total_claims = self
+            .total_claims
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_confirmed_play(&mut self, payout: u64) -> Result<()> {
+        self.total_plays = self
+            .total_plays
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        self.total_earnings = self
+            .total_earnings
+            .checked_add(payout)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        self.update_reputation(BPS_DENOMINATOR);
+        Ok(())
+    }
+
+    pub fn record_timed_out_play(&mut self) -> Result<()> {
+        self.timed_out_plays = self
+            .timed_out_plays
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        self.update_reputation(0);
+        Ok(())
+    }
+
+    pub fn record_lost_dispute(&mut self) -> Result<()> {
+        self.disputes_lost = self
+            .disputes_lost
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        self.update_reputation(0);
+        Ok(())
+    }
+
+    fn update_reputation(&mut self, outcome_bps: u16) {
+        self.reputation_bps = reputation_step(self.reputation_bps, outcome_bps);
+    }
+}
+
+/// Move `score` REPUTATION_ALPHA_BPS of the way towards `outcome_bps`.
+/// Rounds towards the outcome, so a run of confirmed plays reaches 10000
+/// instead of stalling just below it.
+fn reputation_step(score: u16, outcome_bps: u16) -> u16 {
+    let keep = (BPS_DENOMINATOR - REPUTATION_ALPHA_BPS) as u32;
+    let weighted = score as u32 * keep + outcome_bps as u32 * REPUTATION_ALPHA_BPS as u32;
+    let denominator = BPS_DENOMINATOR as u32;
+    let next = if outcome_bps > score {
+        weighted.div_ceil(denominator)
+    } else {
+        weighted / denominator
+    };
+    next as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::INITIAL_REPUTATION_BPS;
+
+    fn settle(mut score: u16, outcome_bps: u16) -> u16 {
+        for _ in 0..1_000 {
+            score = reputation_step(score, outcome_bps);
+        }
+        score
+    }
+
+    #[test]
+    fn confirmed_plays_reach_full_reputation() {
+        assert_eq!(
+            reputation_step(INITIAL_REPUTATION_BPS, BPS_DENOMINATOR),
+            5_250
+        );
+        assert_eq!(
+            settle(INITIAL_REPUTATION_BPS, BPS_DENOMINATOR),
+            BPS_DENOMINATOR
+        );
+        assert_eq!(reputation_step(9_999, BPS_DENOMINATOR), BPS_DENOMINATOR);
+    }
+
+    #[test]
+    fn failures_reach_zero_reputation() {
+        assert_eq!(reputation_step(INITIAL_REPUTATION_BPS, 0), 4_750);
+        assert_eq!(settle(INITIAL_REPUTATION_BPS, 0), 0);
+        assert_eq!(reputation_step(1, 0), 0);
+    }
+}
@@ -0,0 +1,66 @@
+use super::shared::MAX_NODE_DENYLIST_ENTRIES;
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+/// NodeAccount keys and operator authorities barred from claiming one
+/// campaign. Seeded by [NODE_DENYLIST_SEED, campaign.key()].
+#[account]
+#[derive(InitSpace)]
+pub struct NodeDenylist {
+    pub campaign_account: Pubkey,
+    /// Publisher authority that paid the rent and receives it back
+    pub authority: Pubkey,
+    /// Sorted, without duplicates
+    #[max_len(MAX_NODE_DENYLIST_ENTRIES)]
+    pub entries: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl NodeDenylist {
+    /// Load the denylist at its PDA, or None if the publisher has not
+    /// created one.
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.owner != &crate::ID {
+            return Ok(None);
+        }
+        Ok(Some(Self::try_deserialize(
+            &mut &info.try_borrow_data()?[..],
+        )?))
+    }
+
+    pub fn contains(&self, key: &Pubkey) -> bool {
+        self.entries.binary_search(key).is_ok()
+    }
+
+    pub fn insert(&mut self, key: Pubkey) {
+        if let Err(index) = self.entries.binary_search(&key) {
+            self.entries.insert(index, key);
+        }
+    }
+
+    pub fn remove(&mut self, key: &Pubkey) {
+        if let Ok(index) = self.entries.binary_search(key) {
+            self.entries.remove(index);
+        }
+    }
+}
+
+/// Leaf of a node allowlist Merkle tree: sha256(0x00 || key), where key is
+/// a NodeAccount key or an operator authority.
+pub fn allowlist_leaf(key: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0x00], key.as_ref()]).to_bytes()
+}
+
+/// Root of the Merkle tree `proof` places `leaf` in. Parents hash their
+/// children in sorted order, sha256(0x01 || min || max), so a proof is just
+/// the list of siblings from the leaf up.
+pub fn allowlist_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (&node, sibling)
+        } else {
+            (sibling, &node)
+        };
+        hashv(&[&[0x01], left, right]).to_bytes()
+    })
+}
@@ -8,9 +8,25 @@ pub struct PlayRecord {
     pub node_account: Pubkey,
     pub nonce: i64,
     pub claimed_at: i64,
+    /// Confirmation deadline, fixed from the play timeout at claim time
+    pub expires_at: i64,
     pub confirmed_at: i64,
-    pub cid_index: u8,
+    pub creative_index: u8,
     pub payment_amount: u64,
+    pub fee_amount: u64,
+    /// Display time proven for PerSecond campaigns; 0 for PerPlay
+    pub duration_ms: u32,
+    /// Price fixed by a slot auction or booking at claim time; overrides campaign pricing
+    pub fixed_price: Option<u64>,
+    /// Node's estimated_footfall at claim time, which prices Cpm plays
+    pub footfall: u32,
+    /// Booking the play was claimed under, paid from its escrow
+    pub booking: Option<Pubkey>,
+    /// Lamports the operator escrowed in this account at claim time
+    pub bond_amount: u64,
+    /// Campaign budget reserved for the play at claim time; 0 for booked
+    /// plays, which the booking escrow pays
+    pub reserved_amount: u64,
     pub status: PlayStatus,
     pub bump: u8,
 }
@@ -0,0 +1,42 @@
+use super::shared::BPS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+/// Singleton holding protocol-wide parameters.
+/// Seeded by [PROTOCOL_CONFIG_SEED].
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    /// Proposed admin; takes over once it calls accept_protocol_admin
+    pub pending_admin: Option<Pubkey>,
+    /// Wallet receiving protocol fees (token fees go to its token account)
+    pub treasury: Pubkey,
+    /// Share of each bounty paid to the treasury, in basis points
+    pub fee_bps: u16,
+    /// Share of a timed-out play's forfeited bond paid to whoever cranks
+    /// timeout_play; the rest goes to the campaign
+    pub crank_reward_bps: u16,
+    /// Lamports an operator escrows per claim, returned on confirmation
+    pub claim_bond: u64,
+    /// Seconds an operator has to confirm a claimed play
+    pub play_timeout: i64,
+    /// Global kill switch for new campaigns and claims
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    /// Treasury share of `amount`, rounded down.
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        bps_of(amount, self.fee_bps)
+    }
+
+    /// Cranker share of a forfeited claim bond, rounded down.
+    pub fn crank_reward_for(&self, bond: u64) -> u64 {
+        bps_of(bond, self.crank_reward_bps)
+    }
+}
+
+fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
@@ -1,4 +1,4 @@
-use super::shared::PublisherStatus;
+use super::shared::{FrequencyCap, PublisherStatus};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,5 +9,12 @@ pub struct PublisherAccount {
     pub total_spent: u64,
     pub registered_at: i64,
     pub status: PublisherStatus,
+    /// Admin-assigned reason code while Suspended, 0 otherwise
+    pub suspension_reason: u16,
+    /// Minimum seconds between claims of any of the publisher's campaigns
+    /// on the same node
+    pub node_cooldown: i64,
+    /// Most claims of the publisher's campaigns per node per window
+    pub frequency_cap: Option<FrequencyCap>,
     pub bump: u8,
 }
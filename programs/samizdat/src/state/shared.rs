@@ -1,3 +1,5 @@
+use super::geo::GeoRegion;
+use crate::errors::SamizdatError;
 use anchor_lang::prelude::*;
 
 // PDA Seeds
@@ -6,6 +8,14 @@ pub const CAMPAIGN_SEED: &[u8] = b"campaign";
 pub const NODE_ACCOUNT_SEED: &[u8] = b"node_account";
 pub const PLAY_RECORD_SEED: &[u8] = b"play_record";
 pub const COOLDOWN_SEED: &[u8] = b"cooldown";
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+pub const SLOT_AUCTION_SEED: &[u8] = b"slot_auction";
+pub const BID_SEED: &[u8] = b"bid";
+pub const BOOKING_SEED: &[u8] = b"booking";
+pub const GEO_INDEX_SEED: &[u8] = b"geo_index";
+pub const NODE_DENYLIST_SEED: &[u8] = b"node_denylist";
+pub const APPROVED_CAMPAIGN_SEED: &[u8] = b"approved_campaign";
+pub const FREQUENCY_SEED: &[u8] = b"frequency";
 
 // Content Tag Bitmask
 // Protocol-level content categories.
@@ -24,19 +34,87 @@ pub const TAG_NSFW: u64 = 1 << 2;
 pub const TAG_POLITICAL: u64 = 1 << 3;
 pub const TAG_ALCOHOL: u64 = 1 << 4;
 
+// Time
+pub const MS_PER_SECOND: u64 = 1_000;
+pub const SECONDS_PER_HOUR: i64 = 3_600;
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+// CPM Pricing
+// Cpm campaigns pay cpm_rate per IMPRESSIONS_PER_MILLE of a node's footfall.
+pub const IMPRESSIONS_PER_MILLE: u64 = 1_000;
+
+// Dayparting
+// Hour-of-week bitmap: bit (day * 24 + hour), day 0 = Monday, local time.
+pub const HOURS_PER_WEEK: usize = 168;
+pub const DAYPART_BITMAP_LEN: usize = HOURS_PER_WEEK / 8;
+pub const MIN_UTC_OFFSET_MINUTES: i16 = -12 * 60;
+pub const MAX_UTC_OFFSET_MINUTES: i16 = 14 * 60;
+
+// Geo Targeting
+// Coordinates are degrees x 1e7 fixed-point.
+pub const GEO_DEGREE: i64 = 10_000_000;
+// Mean Earth radius used for GeoRegion::Circle distances.
+pub const EARTH_RADIUS_M: u32 = 6_371_000;
+pub const MAX_GEO_RADIUS_M: u32 = 1_000_000;
+// Campaigns register in GeoIndex PDAs for the geohash cells their regions
+// cover, using the longest prefix (up to GEOHASH_PRECISION characters) that
+// needs at most MAX_CAMPAIGN_GEO_CELLS cells. The empty prefix, one cell
+// covering the globe, takes regions too wide for any other.
+pub const GEOHASH_PRECISION: usize = 4;
+
 // Play Confirmation Timeout (seconds)
-pub const PLAY_TIMEOUT_SECONDS: i64 = 300;
+// Default for ProtocolConfig.play_timeout; the admin can change it at runtime.
+pub const DEFAULT_PLAY_TIMEOUT_SECONDS: i64 = 300;
+
+// Claim Nonces
+// Replay window tracked per (campaign, node) in ClaimCooldown.nonce_window.
+pub const NONCE_WINDOW_SIZE: u32 = u64::BITS;
+// Furthest a single claim may move the window forward, so one out-of-order
+// nonce keeps the nonces just below it claimable.
+pub const MAX_NONCE_ADVANCE: u64 = NONCE_WINDOW_SIZE as u64 / 2;
+
+// Protocol Fees
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+// Claim Bonds
+// Default for ProtocolConfig.claim_bond (0.001 SOL).
+pub const DEFAULT_CLAIM_BOND_LAMPORTS: u64 = 1_000_000;
+// Default for ProtocolConfig.crank_reward_bps: half of a forfeited bond goes
+// to the timeout_play cranker, the rest to the campaign.
+pub const DEFAULT_CRANK_REWARD_BPS: u16 = 5_000;
+
+// Node Reputation
+// NodeAccount.reputation_bps is an exponential moving average of play
+// outcomes: each outcome moves the score REPUTATION_ALPHA_BPS of the way
+// towards BPS_DENOMINATOR (confirmed) or 0 (timed out, dispute lost).
+pub const INITIAL_REPUTATION_BPS: u16 = 5_000;
+pub const REPUTATION_ALPHA_BPS: u16 = 500;
+
+// Slot Auctions
+// Share of an unrevealed bid's deposit paid to the node operator when the bid
+// is closed; the rest is returned to the bidder.
+pub const UNREVEALED_BID_PENALTY_BPS: u16 = 5_000;
 
 // Size Limits
-pub const MAX_CIDS: usize = 10;
+pub const MAX_CREATIVES: usize = 10;
 pub const MAX_CID_LENGTH: usize = 200;
+pub const MAX_MIME_TYPE_LENGTH: usize = 32;
 pub const MAX_LANDMARKS: usize = 5;
 pub const MAX_LANDMARK_LENGTH: usize = 32;
 pub const MAX_SCREEN_SIZES: usize = 4;
 pub const MAX_ESTABLISHMENT_TYPES: usize = 5;
 pub const MAX_ESTABLISHMENT_TYPE_LENGTH: usize = 32;
+pub const MAX_NODE_BOOKINGS: usize = 4;
+pub const MAX_NODE_AUCTIONED_SLOTS: usize = 4;
 pub const MAX_REQUIRED_LANDMARKS: usize = 5;
 pub const MAX_REQUIRED_LANDMARK_LENGTH: usize = 32;
+pub const MAX_GEO_REGIONS: usize = 4;
+pub const MAX_POLYGON_VERTICES: usize = 8;
+pub const MAX_CAMPAIGN_GEO_CELLS: usize = 8;
+pub const MAX_GEO_INDEX_CAMPAIGNS: usize = 256;
+pub const MAX_NODE_DENYLIST_ENTRIES: usize = 256;
+pub const MAX_FREQUENCY_CAP_PLAYS: u32 = 24;
+pub const MAX_NODE_COOLDOWN_SECONDS: i64 = 30 * SECONDS_PER_DAY;
 
 // Enums
 
@@ -52,6 +130,8 @@ pub enum CampaignStatus {
     Paused,
     Depleted,
     Closed,
+    Draining,
+    Expired,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -65,7 +145,120 @@ pub enum NodeStatus {
 pub enum PlayStatus {
     Claimed,
     Paid,
-    TimedOut,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AuctionStatus {
+    Open,
+    Settled,
+    Claimed,
+    /// The operator took the slot back from a winner that can no longer
+    /// claim it
+    Released,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BookingStatus {
+    /// Created by the publisher, waiting for the node authority
+    Proposed,
+    /// Accepted; the node only claims the booked campaign during the window
+    Active,
+}
+
+/// An accepted Booking's window, kept on the node so claim_campaign can
+/// tell whether the node is reserved without being passed every booking.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct BookedWindow {
+    pub booking: Pubkey,
+    pub start_at: i64,
+    pub end_at: i64,
+}
+
+impl BookedWindow {
+    pub fn contains(&self, unix_timestamp: i64) -> bool {
+        unix_timestamp >= self.start_at && unix_timestamp < self.end_at
+    }
+
+    pub fn overlaps(&self, start_at: i64, end_at: i64) -> bool {
+        start_at < self.end_at && self.start_at < end_at
+    }
+}
+
+/// A SlotAuction's slot, kept on the node from the auction's opening until
+/// its winner claims it, so claim_campaign can require the auction whenever
+/// the node is inside a slot it sold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct AuctionedSlot {
+    pub slot_auction: Pubkey,
+    pub slot_start: i64,
+    pub slot_end: i64,
+}
+
+impl AuctionedSlot {
+    pub fn contains(&self, unix_timestamp: i64) -> bool {
+        unix_timestamp >= self.slot_start && unix_timestamp < self.slot_end
+    }
+
+    pub fn overlaps(&self, start_at: i64, end_at: i64) -> bool {
+        start_at < self.slot_end && self.slot_start < end_at
+    }
+}
+
+/// How a campaign prices each confirmed play.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PricingModel {
+    /// Flat `bounty_per_play`, limited by `plays_remaining`
+    PerPlay,
+    /// `price_per_second` of display, up to `max_duration_ms` per play,
+    /// limited by the unreserved vault balance
+    PerSecond {
+        price_per_second: u64,
+        max_duration_ms: u32,
+    },
+    /// `cpm_rate` per thousand of the node's `estimated_footfall`, capped at
+    /// `bounty_per_play`, limited by the unreserved vault balance
+    Cpm { cpm_rate: u64 },
+}
+
+impl PricingModel {
+    /// Bounty for `duration_ms` of display at `price_per_second`, rounded down.
+    pub fn per_second_payout(price_per_second: u64, duration_ms: u32) -> Result<u64> {
+        let payout = price_per_second as u128 * duration_ms as u128 / MS_PER_SECOND as u128;
+        u64::try_from(payout).map_err(|_| error!(SamizdatError::ArithmeticOverflow))
+    }
+
+    /// Bounty for a play seen by `footfall` people at `cpm_rate`, rounded down
+    /// and capped at `ceiling`.
+    pub fn cpm_payout(cpm_rate: u64, footfall: u32, ceiling: u64) -> u64 {
+        let payout = cpm_rate as u128 * footfall as u128 / IMPRESSIONS_PER_MILLE as u128;
+        payout.min(ceiling as u128) as u64
+    }
+
+    /// Validate the model and the `bounty_per_play` it is paired with.
+    /// PerSecond campaigns store their maximum payout per play as the bounty;
+    /// Cpm campaigns use it as the per-play ceiling.
+    pub fn validate(&self, bounty_per_play: u64) -> Result<()> {
+        match *self {
+            Self::PerPlay => {}
+            Self::PerSecond {
+                price_per_second,
+                max_duration_ms,
+            } => {
+                require!(
+                    price_per_second > 0 && max_duration_ms > 0,
+                    SamizdatError::InvalidPricing
+                );
+                require!(
+                    bounty_per_play == Self::per_second_payout(price_per_second, max_duration_ms)?,
+                    SamizdatError::InvalidBounty
+                );
+            }
+            Self::Cpm { cpm_rate } => {
+                require!(cpm_rate > 0, SamizdatError::InvalidPricing);
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -90,13 +283,107 @@ pub struct Resolution {
     pub height: u32,
 }
 
+/// Content a campaign can display, with enough metadata for a node to
+/// decide whether it can play it before fetching `cid`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct Creative {
+    #[max_len(MAX_CID_LENGTH)]
+    pub cid: String,
+    /// e.g. "image/png", "video/mp4"
+    #[max_len(MAX_MIME_TYPE_LENGTH)]
+    pub mime_type: String,
+    /// Playback length; for stills, how long the image is shown
+    pub duration_ms: u32,
+    pub byte_size: u64,
+    /// sha256 of the content bytes
+    pub content_hash: [u8; 32],
+    /// Smallest screen resolution the creative can be shown on
+    pub min_resolution: Resolution,
+}
+
+impl Creative {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.cid.is_empty() && self.cid.len() <= MAX_CID_LENGTH,
+            SamizdatError::InvalidCreative
+        );
+        require!(
+            !self.mime_type.is_empty() && self.mime_type.len() <= MAX_MIME_TYPE_LENGTH,
+            SamizdatError::InvalidCreative
+        );
+        require!(
+            self.duration_ms > 0 && self.byte_size > 0,
+            SamizdatError::InvalidCreative
+        );
+        Ok(())
+    }
+
+    /// Require every creative of a campaign to have its own content hash, so
+    /// an operator's approval of a hash covers exactly one creative.
+    pub fn require_distinct_hashes(creatives: &[Creative]) -> Result<()> {
+        for (i, creative) in creatives.iter().enumerate() {
+            require!(
+                creatives[..i]
+                    .iter()
+                    .all(|earlier| earlier.content_hash != creative.content_hash),
+                SamizdatError::DuplicateCreative
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether a screen of `resolution` meets the creative's minimum.
+    pub fn fits(&self, resolution: &Resolution) -> bool {
+        resolution.width >= self.min_resolution.width
+            && resolution.height >= self.min_resolution.height
+    }
+}
+
+/// Weekly schedule of hours a campaign may be claimed in.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
-pub struct GeoBounds {
-    // x 1e7 fixed-points
-    pub min_lat: i64,
-    pub max_lat: i64,
-    pub min_lon: i64,
-    pub max_lon: i64,
+pub struct Daypart {
+    pub hours: [u8; DAYPART_BITMAP_LEN],
+    /// Campaign local time offset from UTC, in minutes
+    pub utc_offset_minutes: i16,
+}
+
+impl Daypart {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            (MIN_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&self.utc_offset_minutes),
+            SamizdatError::InvalidDaypart
+        );
+        Ok(())
+    }
+
+    /// Whether the local hour-of-week containing `unix_timestamp` is enabled.
+    pub fn is_open_at(&self, unix_timestamp: i64) -> bool {
+        let local = unix_timestamp + self.utc_offset_minutes as i64 * 60;
+        let days = local.div_euclid(SECONDS_PER_DAY);
+        // 1970-01-01 was a Thursday (day 3 when Monday = 0)
+        let weekday = (days + 3).rem_euclid(7);
+        let hour = local.rem_euclid(SECONDS_PER_DAY) / SECONDS_PER_HOUR;
+        let bit = (weekday * 24 + hour) as usize;
+        self.hours[bit / 8] & (1 << (bit % 8)) != 0
+    }
+}
+
+/// Most plays a publisher's campaigns, together, may get on one node within
+/// a sliding window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct FrequencyCap {
+    pub max_plays: u32,
+    pub window_seconds: i64,
+}
+
+impl FrequencyCap {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            (1..=MAX_FREQUENCY_CAP_PLAYS).contains(&self.max_plays) && self.window_seconds > 0,
+            SamizdatError::InvalidFrequencyCap
+        );
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -105,9 +392,36 @@ pub struct TargetFilters {
     pub max_footfall: Option<u32>,
     #[max_len(MAX_SCREEN_SIZES)]
     pub screen_sizes: Vec<ScreenSize>,
-    pub geo_bounds: Option<GeoBounds>,
+    /// Node must be inside at least one region when any are set
+    #[max_len(MAX_GEO_REGIONS)]
+    pub geo_regions: Vec<GeoRegion>,
     #[max_len(MAX_ESTABLISHMENT_TYPES, MAX_ESTABLISHMENT_TYPE_LENGTH)]
     pub establishment_types: Vec<String>,
     #[max_len(MAX_REQUIRED_LANDMARKS, MAX_REQUIRED_LANDMARK_LENGTH)]
     pub required_landmarks: Vec<String>,
+    pub daypart: Option<Daypart>,
+    /// Minimum NodeAccount.reputation_bps a node needs to claim
+    pub min_reputation: Option<u16>,
+    /// Merkle root of the NodeAccount keys and operator authorities allowed
+    /// to claim; claims prove membership with a proof (see allowlist_root)
+    pub node_allowlist: Option<[u8; 32]>,
+}
+
+impl TargetFilters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.geo_regions.len() <= MAX_GEO_REGIONS,
+            SamizdatError::InvalidGeoRegion
+        );
+        for region in &self.geo_regions {
+            region.validate()?;
+        }
+        if let Some(daypart) = &self.daypart {
+            daypart.validate()?;
+        }
+        if let Some(min) = self.min_reputation {
+            require!(min <= BPS_DENOMINATOR, SamizdatError::InvalidReputation);
+        }
+        Ok(())
+    }
 }
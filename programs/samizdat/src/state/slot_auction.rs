@@ -0,0 +1,95 @@
+use super::shared::AuctionStatus;
+use crate::errors::SamizdatError;
+use anchor_lang::prelude::*;
+
+/// A time window on one node that campaigns bid for. Bids are committed as
+/// hashes, revealed after the commit window, and the highest revealed bidder
+/// wins the slot at the second-highest bid (or the reserve price).
+#[account]
+#[derive(InitSpace)]
+pub struct SlotAuction {
+    pub node_account: Pubkey,
+    pub slot_id: u64,
+    /// Bid commitments are accepted until this timestamp
+    pub commit_ends_at: i64,
+    /// Committed bids can be revealed until this timestamp
+    pub reveal_ends_at: i64,
+    /// Window in which the node can claim the winning campaign
+    pub slot_start: i64,
+    pub slot_end: i64,
+    /// Minimum bid; the clearing price when fewer than two bids are revealed
+    pub reserve_price: u64,
+    /// Lamports escrowed with each commitment, partly forfeited if not revealed
+    pub bid_deposit: u64,
+    pub bid_count: u32,
+    pub revealed_bids: u32,
+    /// Bid accounts not yet closed; the auction cannot be closed before them
+    pub open_bids: u32,
+    pub highest_bid: u64,
+    pub second_bid: u64,
+    /// Highest-bidding campaign; None until the first reveal
+    pub winner: Option<Pubkey>,
+    /// Price the winner pays, set at settlement
+    pub clearing_price: u64,
+    pub status: AuctionStatus,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl SlotAuction {
+    /// Count a new sealed bid.
+    pub fn record_commit(&mut self) -> Result<()> {
+        self.bid_count = self
+            .bid_count
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        self.open_bids = self
+            .open_bids
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Track the top two revealed bids. Ties go to the earlier reveal.
+    pub fn record_reveal(&mut self, campaign: Pubkey, amount: u64) -> Result<()> {
+        if self.winner.is_none() || amount > self.highest_bid {
+            self.second_bid = self.highest_bid;
+            self.highest_bid = amount;
+            self.winner = Some(campaign);
+        } else if amount > self.second_bid {
+            self.second_bid = amount;
+        }
+        self.revealed_bids = self
+            .revealed_bids
+            .checked_add(1)
+            .ok_or(SamizdatError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Close bidding and fix the second-price clearing price.
+    pub fn settle(&mut self) {
+        if self.winner.is_some() {
+            self.clearing_price = self.second_bid.max(self.reserve_price);
+        }
+        self.status = AuctionStatus::Settled;
+    }
+
+    /// Hand the slot to `campaign` for a claim at `unix_timestamp`,
+    /// returning the price it pays.
+    pub fn claim(&mut self, campaign: Pubkey, unix_timestamp: i64) -> Result<u64> {
+        require!(
+            self.status == AuctionStatus::Settled,
+            SamizdatError::SlotNotClaimable
+        );
+        require!(
+            self.winner == Some(campaign),
+            SamizdatError::NotAuctionWinner
+        );
+        require!(
+            unix_timestamp >= self.slot_start && unix_timestamp < self.slot_end,
+            SamizdatError::SlotNotClaimable
+        );
+        self.status = AuctionStatus::Claimed;
+        Ok(self.clearing_price)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::errors::SamizdatError;
+use crate::state::{CampaignAccount, CAMPAIGN_SEED};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Token accounts backing an SPL-denominated campaign.
+/// The vault is the campaign PDA's associated token account for `mint`.
+pub struct TokenVault<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenVault<'a, 'info> {
+    /// Resolve the optional token accounts passed to an instruction.
+    /// Returns `None` for native SOL campaigns (`mint == None`).
+    pub fn resolve(
+        campaign_mint: Option<Pubkey>,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        let Some(expected_mint) = campaign_mint else {
+            return Ok(None);
+        };
+        let (Some(mint), Some(vault), Some(token_program)) =
+            (mint.as_ref(), vault.as_ref(), token_program.as_ref())
+        else {
+            return err!(SamizdatError::MissingTokenAccounts);
+        };
+        require_keys_eq!(mint.key(), expected_mint, SamizdatError::MintMismatch);
+
+        Ok(Some(Self {
+            mint,
+            vault,
+            token_program,
+        }))
+    }
+}
+
+/// Funds available for bounties: vault token balance for SPL campaigns,
+/// or lamports above the rent-exempt minimum for SOL campaigns.
+pub fn campaign_balance(
+    campaign_info: &AccountInfo,
+    token_vault: Option<&TokenVault>,
+) -> Result<u64> {
+    if let Some(token_vault) = token_vault {
+        return Ok(token_vault.vault.amount);
+    }
+    let rent_exempt_min = Rent::get()?.minimum_balance(campaign_info.data_len());
+    Ok(campaign_info.lamports().saturating_sub(rent_exempt_min))
+}
+
+/// Move `amount` from the publisher into the campaign vault.
+pub fn deposit<'info>(
+    campaign_info: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    authority_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_vault: Option<&TokenVault<'_, 'info>>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    match token_vault {
+        Some(token_vault) => {
+            let from = authority_token_account.ok_or(SamizdatError::MissingTokenAccounts)?;
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token_vault.token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: token_vault.mint.to_account_info(),
+                        to: token_vault.vault.to_account_info(),
+                        authority: authority.clone(),
+                    },
+                ),
+                amount,
+                token_vault.mint.decimals,
+            )
+        }
+        None => system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: authority.clone(),
+                    to: campaign_info.clone(),
+                },
+            ),
+            amount,
+        ),
+    }
+}
+
+/// Pay `amount` out of the campaign vault to `recipient`.
+/// SPL campaigns transfer to `recipient_token_account`, signed by the campaign PDA.
+pub fn pay_out<'info>(
+    campaign: &Account<'info, CampaignAccount>,
+    token_vault: Option<&TokenVault<'_, 'info>>,
+    recipient: &AccountInfo<'info>,
+    recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    amount: u64,
+) -> Result<()> {
+    match token_vault {
+        Some(token_vault) => {
+            let to = recipient_token_account.ok_or(SamizdatError::MissingTokenAccounts)?;
+            let campaign_id = campaign.campaign_id.to_le_bytes();
+            let seeds: &[&[u8]] = &[
+                CAMPAIGN_SEED,
+                campaign.publisher_account.as_ref(),
+                &campaign_id,
+                &[campaign.bump],
+            ];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_vault.token_program.to_account_info(),
+                    TransferChecked {
+                        from: token_vault.vault.to_account_info(),
+                        mint: token_vault.mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: campaign.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                token_vault.mint.decimals,
+            )
+        }
+        None => {
+            let campaign_info = campaign.to_account_info();
+            **campaign_info.try_borrow_mut_lamports()? -= amount;
+            **recipient.try_borrow_mut_lamports()? += amount;
+            Ok(())
+        }
+    }
+}
+
+/// Whether `recipient` can take `amount` from the campaign vault: an
+/// unfrozen token account for SPL campaigns, or a balance that ends up
+/// rent-exempt for SOL campaigns.
+pub fn can_receive(
+    token_vault: Option<&TokenVault>,
+    recipient: &AccountInfo,
+    recipient_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    amount: u64,
+) -> Result<bool> {
+    if token_vault.is_some() {
+        return Ok(recipient_token_account.is_some_and(|account| !account.is_frozen()));
+    }
+    let balance = recipient.lamports().saturating_add(amount);
+    Ok(Rent::get()?.is_exempt(balance, recipient.data_len()))
+}
+
+/// Refund the whole token vault to `recipient_token_account` and close it,
+/// returning the vault's rent to `rent_recipient`.
+pub fn drain_token_vault<'info>(
+    campaign: &Account<'info, CampaignAccount>,
+    token_vault: &TokenVault<'_, 'info>,
+    recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    rent_recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    let remaining = token_vault.vault.amount;
+    if remaining > 0 {
+        pay_out(
+            campaign,
+            Some(token_vault),
+            rent_recipient,
+            recipient_token_account,
+            remaining,
+        )?;
+    }
+
+    let campaign_id = campaign.campaign_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        CAMPAIGN_SEED,
+        campaign.publisher_account.as_ref(),
+        &campaign_id,
+        &[campaign.bump],
+    ];
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_vault.token_program.to_account_info(),
+        CloseAccount {
+            account: token_vault.vault.to_account_info(),
+            destination: rent_recipient.clone(),
+            authority: campaign.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
@@ -1,7 +1,38 @@
-import { describe, test, before } from "node:test";
+import { describe, test, before, after } from "node:test";
 import assert from "node:assert";
 import { connect, type Connection, getPDAAndBump } from "solana-kite";
-import { type Address, type TransactionSigner, lamports } from "@solana/kit";
+import {
+  type Address,
+  type Instruction,
+  AccountRole,
+  type TransactionSigner,
+  address,
+  generateKeyPair,
+  generateKeyPairSigner,
+  getAddressEncoder,
+  getAddressDecoder,
+  getAddressFromPublicKey,
+  getBase58Encoder,
+  getI64Encoder,
+  getU32Encoder,
+  getU64Decoder,
+  getU64Encoder,
+  getProgramDerivedAddress,
+  lamports,
+  signBytes,
+  some,
+  type Signature,
+} from "@solana/kit";
+import { getCreateAccountInstruction } from "@solana-program/system";
+import {
+  TOKEN_PROGRAM_ADDRESS,
+  findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstruction,
+  getInitializeMint2Instruction,
+  getMintSize,
+  getMintToInstruction,
+} from "@solana-program/token";
+import { createHash } from "node:crypto";
 import { readFileSync } from "node:fs";
 import { createKeyPairSignerFromBytes } from "@solana/kit";
 
@@ -11,21 +42,110 @@ import {
   getCreateCampaignInstructionAsync,
   getFundCampaignInstructionAsync,
   getUpdateCampaignInstructionAsync,
-  getAddCidsToCampaignInstructionAsync,
+  getAddCreativesToCampaignInstructionAsync,
   getCloseCampaignInstructionAsync,
   getRegisterNodeInstructionAsync,
-  getUpdateNodeMetadataInstruction,
+  getUpdateNodeMetadataInstructionAsync,
   getClaimCampaignInstructionAsync,
-  getConfirmPlayInstruction,
+  getConfirmPlayInstructionAsync,
+  getClosePlayRecordInstructionAsync,
+  getTimeoutPlayInstructionAsync,
+  getExpireCampaignInstructionAsync,
+  getInitializeProtocolConfigInstructionAsync,
+  getUpdateProtocolConfigInstructionAsync,
+  getTransferProtocolAdminInstructionAsync,
+  getAcceptProtocolAdminInstructionAsync,
+  getSuspendPublisherInstructionAsync,
+  getReinstatePublisherInstructionAsync,
+  getSuspendNodeInstructionAsync,
+  getReinstateNodeInstructionAsync,
+  getRecordLostDisputeInstructionAsync,
+  getOpenSlotAuctionInstructionAsync,
+  getCommitBidInstructionAsync,
+  getRevealBidInstructionAsync,
+  getSettleSlotAuctionInstructionAsync,
+  getReleaseAuctionedSlotInstructionAsync,
+  getCloseBidInstructionAsync,
+  getCloseSlotAuctionInstructionAsync,
+  getCreateBookingInstructionAsync,
+  getAcceptBookingInstructionAsync,
+  getCancelBookingInstructionAsync,
+  getSettleBookingInstructionAsync,
+  getUpdateNodeDenylistInstructionAsync,
+  getCloseNodeDenylistInstructionAsync,
+  getApproveCampaignInstructionAsync,
+  getRevokeCampaignApprovalInstructionAsync,
+  getUpdateFrequencyCapInstructionAsync,
+  fetchMaybeProtocolConfig,
+  fetchProtocolConfig,
   fetchPublisherAccount,
   fetchCampaignAccount,
   fetchNodeAccount,
   fetchPlayRecord,
   fetchClaimCooldown,
+  fetchSlotAuction,
+  fetchBid,
+  fetchBooking,
+  fetchMaybeGeoIndex,
+  fetchNodeDenylist,
+  fetchApprovedCampaign,
   ScreenSize,
   PlayStatus,
+  NodeStatus,
+  PublisherStatus,
+  AuctionStatus,
+  BookingStatus,
+  type GeoRegionArgs,
+  type PricingModelArgs,
   type TargetFiltersArgs,
   CampaignStatus,
+  SAMIZDAT_ERROR__AUCTION_NOT_FINISHED,
+  SAMIZDAT_ERROR__BIDDING_CLOSED,
+  SAMIZDAT_ERROR__BIDDING_NOT_CLOSED,
+  SAMIZDAT_ERROR__BOOKING_MISMATCH,
+  SAMIZDAT_ERROR__BOOKING_NOT_ENDED,
+  SAMIZDAT_ERROR__BOUNTY_BELOW_NODE_FLOOR,
+  SAMIZDAT_ERROR__CAMPAIGN_ENDED,
+  SAMIZDAT_ERROR__CAMPAIGN_NOT_ACTIVE,
+  SAMIZDAT_ERROR__CAMPAIGN_NOT_APPROVED,
+  SAMIZDAT_ERROR__CAMPAIGN_NOT_STARTED,
+  SAMIZDAT_ERROR__COMMITMENT_MISMATCH,
+  SAMIZDAT_ERROR__CONTENT_FILTER_VIOLATION,
+  SAMIZDAT_ERROR__COOLDOWN_NOT_EXPIRED,
+  SAMIZDAT_ERROR__DAILY_CAP_REACHED,
+  SAMIZDAT_ERROR__DUPLICATE_CREATIVE,
+  SAMIZDAT_ERROR__FLIGHT_NOT_ENDED,
+  SAMIZDAT_ERROR__FREQUENCY_CAP_REACHED,
+  SAMIZDAT_ERROR__INVALID_AMOUNT,
+  SAMIZDAT_ERROR__INVALID_BID,
+  SAMIZDAT_ERROR__INVALID_BOOKING,
+  SAMIZDAT_ERROR__INVALID_BOUNTY,
+  SAMIZDAT_ERROR__INVALID_CREATIVE,
+  SAMIZDAT_ERROR__INVALID_DURATION,
+  SAMIZDAT_ERROR__INVALID_FEE,
+  SAMIZDAT_ERROR__INVALID_GEO_INDEX,
+  SAMIZDAT_ERROR__INVALID_GEO_REGION,
+  SAMIZDAT_ERROR__INVALID_PLAYS,
+  SAMIZDAT_ERROR__INVALID_PRICING,
+  SAMIZDAT_ERROR__INVALID_PROOF,
+  SAMIZDAT_ERROR__INVALID_REPUTATION,
+  SAMIZDAT_ERROR__INVALID_STATUS_TRANSITION,
+  SAMIZDAT_ERROR__NODE_BOOKED,
+  SAMIZDAT_ERROR__NODE_DENYLISTED,
+  SAMIZDAT_ERROR__NODE_NOT_ACTIVE,
+  SAMIZDAT_ERROR__NODE_NOT_ALLOWLISTED,
+  SAMIZDAT_ERROR__NONCE_ALREADY_USED,
+  SAMIZDAT_ERROR__NOT_AUCTION_WINNER,
+  SAMIZDAT_ERROR__OUTSIDE_DAYPART,
+  SAMIZDAT_ERROR__PLAY_NOT_SETTLED,
+  SAMIZDAT_ERROR__PROTOCOL_PAUSED,
+  SAMIZDAT_ERROR__PUBLISHER_NOT_ACTIVE,
+  SAMIZDAT_ERROR__RESOLUTION_TOO_LOW,
+  SAMIZDAT_ERROR__REVEAL_NOT_OPEN,
+  SAMIZDAT_ERROR__SLOT_AUCTIONED,
+  SAMIZDAT_ERROR__TARGET_MISMATCH,
+  SAMIZDAT_ERROR__UNAUTHORIZED,
+  SAMIZDAT_ERROR__WINNER_CAN_STILL_CLAIM,
 } from "@client/index";
 
 const RUN_SEED = BigInt(Date.now());
@@ -36,10 +156,24 @@ const TAG_MASK = 0n; // no content tags
 const CLAIM_COOLDOWN = 0n; // no cooldown for happy-path tests
 const NODE_ID = RUN_SEED;
 const CLAIM_NONCE = 1n;
-const CID_INDEX = 0;
+const CREATIVE_INDEX = 0;
+const PROTOCOL_FEE_BPS = 500; // 5% of each bounty to the treasury
+const PER_PLAY: PricingModelArgs = { __kind: "PerPlay" };
+
+const sampleCreative = (
+  cid: string,
+  minResolution = { width: 1280, height: 720 },
+) => ({
+  cid,
+  mimeType: "video/mp4",
+  durationMs: 15_000,
+  byteSize: 2_500_000n,
+  contentHash: new Uint8Array(createHash("sha256").update(cid).digest()),
+  minResolution,
+});
 
-const SAMPLE_CIDS = [
-  "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+const SAMPLE_CREATIVES = [
+  sampleCreative("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
 ];
 
 const SAMPLE_LOCATION = {
@@ -53,23 +187,242 @@ const SAMPLE_TARGET_FILTERS: TargetFiltersArgs = {
   minFootfall: null,
   maxFootfall: null,
   screenSizes: [],
-  geoBounds: null,
+  geoRegions: [],
   establishmentTypes: [],
   requiredLandmarks: [],
+  daypart: null,
+  minReputation: null,
+  nodeAllowlist: null,
 };
 
 const CLUSTER = (process.env.CLUSTER ?? "localnet") as "localnet" | "devnet";
 
+const TOKEN_EXTENSIONS_PROGRAM_ADDRESS = address(
+  "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+);
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ADDRESS = address(
+  "BPFLoaderUpgradeab1e11111111111111111111111",
+);
+const ED25519_PROGRAM_ADDRESS = address(
+  "Ed25519SigVerify111111111111111111111111111",
+);
+const PROOF_DOMAIN_V1 = new TextEncoder().encode("samizdat:proof:v1");
+const PROOF_DOMAIN_V2 = new TextEncoder().encode("samizdat:proof:v2");
+
+/** Mirrors `ProofPayloadV1::to_signable_bytes` on-chain. */
+function proofMessage(
+  campaign: Address,
+  node: Address,
+  nonce: bigint,
+  timestamp: bigint,
+): Uint8Array {
+  const addressEncoder = getAddressEncoder();
+  const i64Encoder = getI64Encoder();
+  return new Uint8Array([
+    ...PROOF_DOMAIN_V1,
+    ...addressEncoder.encode(campaign),
+    ...addressEncoder.encode(node),
+    ...i64Encoder.encode(nonce),
+    ...i64Encoder.encode(timestamp),
+  ]);
+}
+
+/** Mirrors `ProofPayloadV2::to_signable_bytes` on-chain. */
+function proofMessageV2(
+  campaign: Address,
+  node: Address,
+  nonce: bigint,
+  timestamp: bigint,
+  durationMs: number,
+): Uint8Array {
+  const v1 = proofMessage(campaign, node, nonce, timestamp);
+  return new Uint8Array([
+    ...PROOF_DOMAIN_V2,
+    ...v1.subarray(PROOF_DOMAIN_V1.length),
+    ...getU32Encoder().encode(durationMs),
+  ]);
+}
+
+/** Mirrors `Bid::commitment` on-chain. */
+function bidCommitment(
+  amount: bigint,
+  salt: Uint8Array,
+  campaign: Address,
+): Uint8Array {
+  return new Uint8Array(
+    createHash("sha256")
+      .update(getU64Encoder().encode(amount) as Uint8Array)
+      .update(salt)
+      .update(getAddressEncoder().encode(campaign) as Uint8Array)
+      .digest(),
+  );
+}
+
+/** Mirrors `allowlist_leaf` on-chain. */
+function allowlistLeaf(key: Address): Uint8Array {
+  return new Uint8Array(
+    createHash("sha256")
+      .update(new Uint8Array([0x00]))
+      .update(getAddressEncoder().encode(key) as Uint8Array)
+      .digest(),
+  );
+}
+
+/** Mirrors the sorted-pair parent hash of `allowlist_root` on-chain. */
+function allowlistParent(a: Uint8Array, b: Uint8Array): Uint8Array {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return new Uint8Array(
+    createHash("sha256")
+      .update(new Uint8Array([0x01]))
+      .update(left)
+      .update(right)
+      .digest(),
+  );
+}
+
+/** Builds an Ed25519 program instruction with signature, key and message inlined. */
+async function ed25519ProofInstruction(
+  renderer: CryptoKeyPair,
+  message: Uint8Array,
+): Promise<Instruction> {
+  const publicKey = getAddressEncoder().encode(
+    await getAddressFromPublicKey(renderer.publicKey),
+  );
+  const signature = await signBytes(renderer.privateKey, message);
+
+  const HEADER_LEN = 16;
+  const pubkeyOffset = HEADER_LEN;
+  const signatureOffset = pubkeyOffset + 32;
+  const messageOffset = signatureOffset + 64;
+
+  const data = new Uint8Array(messageOffset + message.length);
+  const view = new DataView(data.buffer);
+  data[0] = 1; // num_signatures
+  view.setUint16(2, signatureOffset, true);
+  view.setUint16(4, 0xffff, true);
+  view.setUint16(6, pubkeyOffset, true);
+  view.setUint16(8, 0xffff, true);
+  view.setUint16(10, messageOffset, true);
+  view.setUint16(12, message.length, true);
+  view.setUint16(14, 0xffff, true);
+  data.set(publicKey, pubkeyOffset);
+  data.set(signature, signatureOffset);
+  data.set(message, messageOffset);
+
+  return { programAddress: ED25519_PROGRAM_ADDRESS, data };
+}
+
+// `emit_cpi!` prefixes event data with this tag before the event discriminator
+const EVENT_IX_TAG = new Uint8Array([
+  0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d,
+]);
+
+/** Returns the payloads of every `name` event the program emitted in a transaction. */
+async function getEmittedEvents(
+  connection: Connection,
+  signature: Signature,
+  name: string,
+): Promise<Uint8Array[]> {
+  const tx = await connection.rpc
+    .getTransaction(signature, {
+      commitment: "confirmed",
+      encoding: "json",
+      maxSupportedTransactionVersion: 0,
+    })
+    .send();
+  assert.ok(tx, `transaction ${signature} not found`);
+
+  const accountKeys = [
+    ...tx.transaction.message.accountKeys,
+    ...(tx.meta?.loadedAddresses?.writable ?? []),
+    ...(tx.meta?.loadedAddresses?.readonly ?? []),
+  ];
+  const discriminator = createHash("sha256")
+    .update(`event:${name}`)
+    .digest()
+    .subarray(0, 8);
+  const prefix = new Uint8Array([...EVENT_IX_TAG, ...discriminator]);
+
+  return (tx.meta?.innerInstructions ?? [])
+    .flatMap((inner) => inner.instructions)
+    .filter(
+      (ix) => accountKeys[ix.programIdIndex] === SAMIZDAT_PROGRAM_ADDRESS,
+    )
+    .map((ix) => new Uint8Array(getBase58Encoder().encode(ix.data)))
+    .filter((data) => prefix.every((byte, i) => data[i] === byte))
+    .map((data) => data.subarray(prefix.length));
+}
+
+/** Reads the pubkey at `offset` of an event payload. */
+const eventAddress = (data: Uint8Array, offset: number) =>
+  getAddressDecoder().decode(data.subarray(offset, offset + 32));
+
+/** Reads the u64 at `offset` of an event payload. */
+const eventU64 = (data: Uint8Array, offset: number) =>
+  getU64Decoder().decode(data.subarray(offset, offset + 8));
+
+/** Derives the GeoIndex PDA of a geohash cell. */
+const geoIndexAddress = async (cell: string) =>
+  (await getPDAAndBump(SAMIZDAT_PROGRAM_ADDRESS, ["geo_index", cell])).pda;
+
+/** Anchor's error for an account that does not exist. */
+const ACCOUNT_NOT_INITIALIZED = 3012;
+
+type ErrorLike = {
+  message?: string;
+  cause?: ErrorLike;
+  context?: { code?: unknown; logs?: string[] };
+};
+
+/**
+ * Matches a rejection caused by program error `code`, whether it surfaces as
+ * a custom instruction error in the cause chain or only in the program logs.
+ */
+const programError = (code: number) => (error: unknown) => {
+  const custom = new RegExp(`custom program error: 0x${code.toString(16)}\\b`);
+  for (let e = error as ErrorLike | undefined; e; e = e.cause) {
+    if (e.context?.code === code) return true;
+    const text = [e.message ?? "", ...(e.context?.logs ?? [])].join("\n");
+    if (custom.test(text) || text.includes(`Error Number: ${code}.`)) {
+      return true;
+    }
+  }
+  return false;
+};
+
+/** Appends the GeoIndex PDAs of `cells`, in order, as remaining accounts. */
+async function withGeoIndexes<T extends Instruction>(
+  ix: T,
+  cells: string[],
+): Promise<T> {
+  const indexes = await Promise.all(cells.map(geoIndexAddress));
+  return {
+    ...ix,
+    accounts: [
+      ...(ix.accounts ?? []),
+      ...indexes.map((address) => ({ address, role: AccountRole.WRITABLE })),
+    ],
+  };
+}
+
 describe("Samizdat Program – Happy Path", () => {
   let connection: Connection;
   let publisher: TransactionSigner;
   let operator: TransactionSigner;
+  let renderer: CryptoKeyPair;
+  let admin: TransactionSigner;
+  let treasury: Address;
+  let feeBps: number;
+
+  /** Treasury share of a bounty, mirroring `ProtocolConfig::fee_for`. */
+  const feeFor = (amount: bigint) => (amount * BigInt(feeBps)) / 10_000n;
 
   let publisherAccountPDA: Address;
   let campaignAccountPDA: Address;
   let nodeAccountPDA: Address;
   let playRecordPDA: Address;
   let claimCooldownPDA: Address;
+  let protocolConfigPDA: Address;
 
   // Snapshot values captured before each section
   let initialTotalCampaigns: bigint;
@@ -101,6 +454,15 @@ describe("Samizdat Program – Happy Path", () => {
       );
     }
 
+    renderer = await generateKeyPair();
+
+    // The provider wallet deploys the program, so it is the upgrade authority
+    const walletPath =
+      process.env.ANCHOR_WALLET ?? `${process.env.HOME}/.config/solana/id.json`;
+    admin = await createKeyPairSignerFromBytes(
+      new Uint8Array(JSON.parse(readFileSync(walletPath, "utf-8"))),
+    );
+
     ({ pda: publisherAccountPDA } = await getPDAAndBump(
       SAMIZDAT_PROGRAM_ADDRESS,
       ["publisher", publisher.address],
@@ -130,6 +492,11 @@ describe("Samizdat Program – Happy Path", () => {
       nodeAccountPDA,
     ]));
 
+    ({ pda: protocolConfigPDA } = await getPDAAndBump(
+      SAMIZDAT_PROGRAM_ADDRESS,
+      ["protocol_config"],
+    ));
+
     console.log("accounts:", {
       publisher: publisher.address,
       operator: operator.address,
@@ -138,8 +505,34 @@ describe("Samizdat Program – Happy Path", () => {
       nodeAccountPDA,
       playRecordPDA,
       claimCooldownPDA,
+      protocolConfigPDA,
     });
 
+    // Bootstrap the protocol config once per deployment
+    const existingConfig = await fetchMaybeProtocolConfig(
+      connection.rpc,
+      protocolConfigPDA,
+    );
+    if (!existingConfig.exists) {
+      const [programData] = await getProgramDerivedAddress({
+        programAddress: BPF_LOADER_UPGRADEABLE_PROGRAM_ADDRESS,
+        seeds: [getAddressEncoder().encode(SAMIZDAT_PROGRAM_ADDRESS)],
+      });
+      const ix = await getInitializeProtocolConfigInstructionAsync({
+        programData,
+        authority: admin,
+        treasury: admin.address,
+        feeBps: PROTOCOL_FEE_BPS,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [ix],
+      });
+    }
+    const config = await fetchProtocolConfig(connection.rpc, protocolConfigPDA);
+    treasury = config.data.treasury;
+    feeBps = config.data.feeBps;
+
     // Register publisher if not already registered (idempotent)
     try {
       const ix = await getRegisterPublisherInstructionAsync({
@@ -178,15 +571,19 @@ describe("Samizdat Program – Happy Path", () => {
       const ix = await getCreateCampaignInstructionAsync({
         authority: publisher,
         campaignId: CAMPAIGN_ID,
-        cids: SAMPLE_CIDS,
+        creatives: SAMPLE_CREATIVES,
         bountyPerPlay: BOUNTY_PER_PLAY,
+        pricing: PER_PLAY,
         totalPlays: TOTAL_PLAYS,
         tagMask: TAG_MASK,
         targetFilters: SAMPLE_TARGET_FILTERS,
         claimCooldown: CLAIM_COOLDOWN,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
       });
 
-      await connection.sendTransactionFromInstructions({
+      const signature = await connection.sendTransactionFromInstructions({
         feePayer: publisher,
         instructions: [ix],
       });
@@ -196,6 +593,17 @@ describe("Samizdat Program – Happy Path", () => {
         campaignAccountPDA,
       );
 
+      // CampaignCreated { campaign, publisher, campaign_id, .. }
+      const [created] = await getEmittedEvents(
+        connection,
+        signature,
+        "CampaignCreated",
+      );
+      assert.ok(created, "CampaignCreated not emitted");
+      assert.strictEqual(eventAddress(created, 0), campaignAccountPDA);
+      assert.strictEqual(eventAddress(created, 32), publisherAccountPDA);
+      assert.strictEqual(eventU64(created, 64), CAMPAIGN_ID);
+
       assert.strictEqual(campaign.data.publisherAccount, publisherAccountPDA);
       assert.strictEqual(campaign.data.campaignId, CAMPAIGN_ID);
       assert.strictEqual(campaign.data.bountyPerPlay, BOUNTY_PER_PLAY);
@@ -204,7 +612,7 @@ describe("Samizdat Program – Happy Path", () => {
       assert.strictEqual(campaign.data.tagMask, TAG_MASK);
       assert.strictEqual(campaign.data.status, CampaignStatus.Active);
       assert.strictEqual(campaign.data.claimCooldown, CLAIM_COOLDOWN);
-      assert.deepStrictEqual(campaign.data.cids, SAMPLE_CIDS);
+      assert.deepStrictEqual(campaign.data.creatives, SAMPLE_CREATIVES);
 
       // Publisher total_campaigns incremented
       const pub = await fetchPublisherAccount(
@@ -248,6 +656,10 @@ describe("Samizdat Program – Happy Path", () => {
         tagMask: null,
         targetFilters: null,
         status: CampaignStatus.Paused,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+        bountyPerPlay: null,
       });
 
       await connection.sendTransactionFromInstructions({
@@ -269,6 +681,10 @@ describe("Samizdat Program – Happy Path", () => {
         tagMask: null,
         targetFilters: null,
         status: CampaignStatus.Active,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+        bountyPerPlay: null,
       });
 
       await connection.sendTransactionFromInstructions({
@@ -283,14 +699,17 @@ describe("Samizdat Program – Happy Path", () => {
       assert.strictEqual(campaign.data.status, CampaignStatus.Active);
     });
 
-    test("adds CIDs to the campaign", async () => {
-      const newCid =
-        "bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq";
+    test("adds creatives to the campaign", async () => {
+      // 4K-only creative, larger than the node registered below
+      const newCreative = sampleCreative(
+        "bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq",
+        { width: 3840, height: 2160 },
+      );
 
-      const ix = await getAddCidsToCampaignInstructionAsync({
+      const ix = await getAddCreativesToCampaignInstructionAsync({
         campaignAccount: campaignAccountPDA,
         authority: publisher,
-        newCids: [newCid],
+        newCreatives: [newCreative],
       });
 
       await connection.sendTransactionFromInstructions({
@@ -302,8 +721,30 @@ describe("Samizdat Program – Happy Path", () => {
         connection.rpc,
         campaignAccountPDA,
       );
-      assert.strictEqual(campaign.data.cids.length, 2);
-      assert.strictEqual(campaign.data.cids[1], newCid);
+      assert.strictEqual(campaign.data.creatives.length, 2);
+      assert.deepStrictEqual(campaign.data.creatives[1], newCreative);
+    });
+
+    test("rejects creatives without a duration", async () => {
+      const ix = await getAddCreativesToCampaignInstructionAsync({
+        campaignAccount: campaignAccountPDA,
+        authority: publisher,
+        newCreatives: [
+          {
+            ...sampleCreative(
+              "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+            ),
+            durationMs: 0,
+          },
+        ],
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_CREATIVE),
+      );
     });
   });
 
@@ -312,6 +753,7 @@ describe("Samizdat Program – Happy Path", () => {
       const ix = await getRegisterNodeInstructionAsync({
         authority: operator,
         nodeId: NODE_ID,
+        signingKey: await getAddressFromPublicKey(renderer.publicKey),
         location: SAMPLE_LOCATION,
         screenSize: ScreenSize.Large,
         resolution: SAMPLE_RESOLUTION,
@@ -330,6 +772,10 @@ describe("Samizdat Program – Happy Path", () => {
 
       assert.strictEqual(node.data.authority, operator.address);
       assert.strictEqual(node.data.nodeId, NODE_ID);
+      assert.strictEqual(
+        node.data.signingKey,
+        await getAddressFromPublicKey(renderer.publicKey),
+      );
       assert.strictEqual(node.data.screenSize, ScreenSize.Large);
       assert.strictEqual(node.data.resolution.width, 1920);
       assert.strictEqual(node.data.resolution.height, 1080);
@@ -337,26 +783,41 @@ describe("Samizdat Program – Happy Path", () => {
       assert.strictEqual(node.data.establishmentType, "retail");
       assert.strictEqual(node.data.totalPlays, 0n);
       assert.strictEqual(node.data.totalEarnings, 0n);
+      assert.strictEqual(node.data.totalClaims, 0n);
+      assert.strictEqual(node.data.reputationBps, 5000);
       assert.deepStrictEqual(node.data.landmarks, ["Times Square"]);
     });
 
     test("updates node metadata", async () => {
-      const ix = getUpdateNodeMetadataInstruction({
+      const ix = await getUpdateNodeMetadataInstructionAsync({
         nodeAccount: nodeAccountPDA,
         authority: operator,
         location: null,
         estimatedFootfall: 8000,
         blockedTagMask: null,
+        requiredTagMask: null,
+        minBountyPerPlay: null,
+        requiresApproval: null,
         status: null,
+        signingKey: null,
       });
 
-      await connection.sendTransactionFromInstructions({
+      const signature = await connection.sendTransactionFromInstructions({
         feePayer: operator,
         instructions: [ix],
       });
 
       const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
       assert.strictEqual(node.data.estimatedFootfall, 8000);
+
+      // NodeUpdated { node, location, .. }
+      const [updated] = await getEmittedEvents(
+        connection,
+        signature,
+        "NodeUpdated",
+      );
+      assert.ok(updated, "NodeUpdated not emitted");
+      assert.strictEqual(eventAddress(updated, 0), nodeAccountPDA);
       // Unchanged fields remain the same
       assert.strictEqual(node.data.screenSize, ScreenSize.Large);
       assert.strictEqual(node.data.establishmentType, "retail");
@@ -364,6 +825,25 @@ describe("Samizdat Program – Happy Path", () => {
   });
 
   describe("Play Cycle - Claim & Confirm", () => {
+    test("node cannot claim a creative above its resolution", async () => {
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: 1,
+        claimNonce: CLAIM_NONCE,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__RESOLUTION_TOO_LOW),
+      );
+    });
+
     test("operator claims a campaign", async () => {
       const campaignBefore = await fetchCampaignAccount(
         connection.rpc,
@@ -372,25 +852,46 @@ describe("Samizdat Program – Happy Path", () => {
 
       const ix = await getClaimCampaignInstructionAsync({
         campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
         nodeAccount: nodeAccountPDA,
         authority: operator,
-        cidIndex: CID_INDEX,
+        creativeIndex: CREATIVE_INDEX,
         claimNonce: CLAIM_NONCE,
+        allowlistProof: null,
       });
 
-      await connection.sendTransactionFromInstructions({
+      const signature = await connection.sendTransactionFromInstructions({
         feePayer: operator,
         instructions: [ix],
       });
 
+      // PlayClaimed { play_record, campaign, node, .. }
+      const [claimed] = await getEmittedEvents(
+        connection,
+        signature,
+        "PlayClaimed",
+      );
+      assert.ok(claimed, "PlayClaimed not emitted");
+      assert.strictEqual(eventAddress(claimed, 0), playRecordPDA);
+      assert.strictEqual(eventAddress(claimed, 32), campaignAccountPDA);
+      assert.strictEqual(eventAddress(claimed, 64), nodeAccountPDA);
+
       // Verify PlayRecord
       const play = await fetchPlayRecord(connection.rpc, playRecordPDA);
       assert.strictEqual(play.data.campaignAccount, campaignAccountPDA);
       assert.strictEqual(play.data.nodeAccount, nodeAccountPDA);
       assert.strictEqual(play.data.nonce, CLAIM_NONCE);
-      assert.strictEqual(play.data.cidIndex, CID_INDEX);
+      assert.strictEqual(play.data.creativeIndex, CREATIVE_INDEX);
       assert.strictEqual(play.data.status, PlayStatus.Claimed);
       assert.strictEqual(play.data.paymentAmount, 0n);
+      assert.strictEqual(play.data.feeAmount, 0n);
+
+      // Claim bond is escrowed in the PlayRecord on top of its rent
+      const config = await fetchProtocolConfig(
+        connection.rpc,
+        protocolConfigPDA,
+      );
+      assert.strictEqual(play.data.bondAmount, config.data.claimBond);
 
       // Verify ClaimCooldown was created
       const cooldown = await fetchClaimCooldown(
@@ -401,7 +902,7 @@ describe("Samizdat Program – Happy Path", () => {
       assert.strictEqual(cooldown.data.node, nodeAccountPDA);
       assert.ok(cooldown.data.lastClaimedAt > 0n);
 
-      // Campaign plays_remaining decremented
+      // Campaign plays_remaining decremented and the bounty held
       const campaignAfter = await fetchCampaignAccount(
         connection.rpc,
         campaignAccountPDA,
@@ -410,6 +911,10 @@ describe("Samizdat Program – Happy Path", () => {
         campaignAfter.data.playsRemaining,
         campaignBefore.data.playsRemaining - 1n,
       );
+      assert.strictEqual(
+        campaignAfter.data.reservedBudget,
+        campaignBefore.data.reservedBudget + campaignBefore.data.bountyPerPlay,
+      );
     });
 
     test("publisher confirms the play and pays the node", async () => {
@@ -417,24 +922,58 @@ describe("Samizdat Program – Happy Path", () => {
         operator.address,
         "confirmed",
       );
+      const treasuryBalanceBefore = await connection.getLamportBalance(
+        treasury,
+        "confirmed",
+      );
+      const fee = feeFor(BOUNTY_PER_PLAY);
 
-      const ix = getConfirmPlayInstruction({
+      // Renderer signs the proof at display time (>= claimed_at)
+      const claimed = await fetchPlayRecord(connection.rpc, playRecordPDA);
+      const proofTimestamp = claimed.data.claimedAt;
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessage(
+          campaignAccountPDA,
+          nodeAccountPDA,
+          CLAIM_NONCE,
+          proofTimestamp,
+        ),
+      );
+
+      const ix = await getConfirmPlayInstructionAsync({
         playRecord: playRecordPDA,
         campaignAccount: campaignAccountPDA,
         publisherAccount: publisherAccountPDA,
         nodeAccount: nodeAccountPDA,
         authority: operator,
+        treasury,
+        proofTimestamp,
+        durationMs: null,
       });
 
-      await connection.sendTransactionFromInstructions({
+      const signature = await connection.sendTransactionFromInstructions({
         feePayer: publisher,
-        instructions: [ix],
+        instructions: [proofIx, ix],
       });
 
+      // PlayConfirmed { play_record, campaign, node, operator, payment_amount, fee_amount, .. }
+      const [confirmed] = await getEmittedEvents(
+        connection,
+        signature,
+        "PlayConfirmed",
+      );
+      assert.ok(confirmed, "PlayConfirmed not emitted");
+      assert.strictEqual(eventAddress(confirmed, 0), playRecordPDA);
+      assert.strictEqual(eventAddress(confirmed, 96), operator.address);
+      assert.strictEqual(eventU64(confirmed, 128), BOUNTY_PER_PLAY - fee);
+      assert.strictEqual(eventU64(confirmed, 136), fee);
+
       // PlayRecord transitions to Paid
       const play = await fetchPlayRecord(connection.rpc, playRecordPDA);
       assert.strictEqual(play.data.status, PlayStatus.Paid);
-      assert.strictEqual(play.data.paymentAmount, BOUNTY_PER_PLAY);
+      assert.strictEqual(play.data.paymentAmount, BOUNTY_PER_PLAY - fee);
+      assert.strictEqual(play.data.feeAmount, fee);
       assert.ok(play.data.confirmedAt > 0n);
 
       // Campaign plays_completed incremented
@@ -444,17 +983,30 @@ describe("Samizdat Program – Happy Path", () => {
       );
       assert.strictEqual(campaign.data.playsCompleted, 1n);
 
-      // Node operator received the bounty
       const nodeBalanceAfter = await connection.getLamportBalance(
         operator.address,
         "confirmed",
       );
-      assert.ok(nodeBalanceAfter > nodeBalanceBefore);
+      // Node operator received the bounty net of fees, plus the claim bond back
+      assert.strictEqual(
+        nodeBalanceAfter - nodeBalanceBefore,
+        BOUNTY_PER_PLAY - fee + claimed.data.bondAmount,
+      );
+
+      // Treasury received the protocol fee
+      const treasuryBalanceAfter = await connection.getLamportBalance(
+        treasury,
+        "confirmed",
+      );
+      assert.strictEqual(treasuryBalanceAfter - treasuryBalanceBefore, fee);
 
       // Node aggregate stats updated
       const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
       assert.strictEqual(node.data.totalPlays, 1n);
-      assert.strictEqual(node.data.totalEarnings, BOUNTY_PER_PLAY);
+      assert.strictEqual(node.data.totalEarnings, BOUNTY_PER_PLAY - fee);
+      assert.strictEqual(node.data.totalClaims, 1n);
+      // One confirmed play moves reputation 5% of the way to 10000
+      assert.strictEqual(node.data.reputationBps, 5250);
 
       // Publisher total_spent updated
       const pub = await fetchPublisherAccount(
@@ -468,35 +1020,209 @@ describe("Samizdat Program – Happy Path", () => {
     });
   });
 
+  describe("Proof of Play", () => {
+    const FORGED_NONCE = 3n;
+    let forgedPlayRecordPDA: Address;
+
+    before(async () => {
+      ({ pda: forgedPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", campaignAccountPDA, nodeAccountPDA, FORGED_NONCE],
+      ));
+
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: FORGED_NONCE,
+        allowlistProof: null,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [ix],
+      });
+    });
+
+    test("rejects a confirmation signed by a key other than the renderer", async () => {
+      const play = await fetchPlayRecord(connection.rpc, forgedPlayRecordPDA);
+      const impostor = await generateKeyPair();
+      const proofIx = await ed25519ProofInstruction(
+        impostor,
+        proofMessage(
+          campaignAccountPDA,
+          nodeAccountPDA,
+          FORGED_NONCE,
+          play.data.claimedAt,
+        ),
+      );
+
+      const ix = await getConfirmPlayInstructionAsync({
+        playRecord: forgedPlayRecordPDA,
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        proofTimestamp: play.data.claimedAt,
+        durationMs: null,
+      });
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [proofIx, ix],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_PROOF),
+      );
+
+      const after = await fetchPlayRecord(connection.rpc, forgedPlayRecordPDA);
+      assert.strictEqual(after.data.status, PlayStatus.Claimed);
+    });
+
+    test("rejects a confirmation without a proof instruction", async () => {
+      const play = await fetchPlayRecord(connection.rpc, forgedPlayRecordPDA);
+      const ix = await getConfirmPlayInstructionAsync({
+        playRecord: forgedPlayRecordPDA,
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        proofTimestamp: play.data.claimedAt,
+        durationMs: null,
+      });
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_PROOF),
+      );
+    });
+  });
+
+  describe("Play Record Rent", () => {
+    test("rejects closing a play record that is still claimed", async () => {
+      // Nonce 3 was claimed in "Proof of Play" and never confirmed
+      const { pda: claimedPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", campaignAccountPDA, nodeAccountPDA, 3n],
+      );
+      const ix = await getClosePlayRecordInstructionAsync({
+        playRecord: claimedPlayRecordPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__PLAY_NOT_SETTLED),
+      );
+    });
+
+    test("operator reclaims rent from a paid play record", async () => {
+      const rent = await connection.getLamportBalance(playRecordPDA);
+      assert.ok(rent > 0n);
+
+      const ix = await getClosePlayRecordInstructionAsync({
+        playRecord: playRecordPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      assert.strictEqual(await connection.getLamportBalance(playRecordPDA), 0n);
+
+      const cooldown = await fetchClaimCooldown(
+        connection.rpc,
+        claimCooldownPDA,
+      );
+      assert.ok(cooldown.data.nonceHigh >= CLAIM_NONCE);
+    });
+
+    test("a closed play record's nonce cannot be claimed again", async () => {
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: CLAIM_NONCE,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__NONCE_ALREADY_USED),
+      );
+    });
+  });
+
   describe("Timeout Play", () => {
     const TIMEOUT_NONCE = 2n;
     let timeoutPlayRecordPDA: Address;
 
+    const setPlayTimeout = (playTimeout: bigint) =>
+      getUpdateProtocolConfigInstructionAsync({
+        admin,
+        treasury: null,
+        feeBps: null,
+        crankRewardBps: null,
+        claimBond: null,
+        playTimeout,
+        paused: null,
+      });
+
     before(async () => {
       ({ pda: timeoutPlayRecordPDA } = await getPDAAndBump(
         SAMIZDAT_PROGRAM_ADDRESS,
         ["play_record", campaignAccountPDA, nodeAccountPDA, TIMEOUT_NONCE],
       ));
 
-      // Claim a second play that we'll attempt to time out
+      // Claim a second play that we'll attempt to time out, under a short
+      // confirmation window so it expires without clock warping
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [await setPlayTimeout(1n)],
+      });
       const ix = await getClaimCampaignInstructionAsync({
         campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
         nodeAccount: nodeAccountPDA,
         authority: operator,
-        cidIndex: CID_INDEX,
+        creativeIndex: CREATIVE_INDEX,
         claimNonce: TIMEOUT_NONCE,
+        allowlistProof: null,
       });
 
       await connection.sendTransactionFromInstructions({
         feePayer: operator,
         instructions: [ix],
       });
+
+      // The claim keeps its deadline after the timeout is restored
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [await setPlayTimeout(300n)],
+      });
     });
 
     test("verifies the claimed play exists before timeout", async () => {
       const play = await fetchPlayRecord(connection.rpc, timeoutPlayRecordPDA);
       assert.strictEqual(play.data.status, PlayStatus.Claimed);
       assert.strictEqual(play.data.nonce, TIMEOUT_NONCE);
+      assert.strictEqual(play.data.expiresAt, play.data.claimedAt + 1n);
 
       // plays_remaining should have decremented again
       const campaign = await fetchCampaignAccount(
@@ -505,58 +1231,3685 @@ describe("Samizdat Program – Happy Path", () => {
       );
       assert.strictEqual(
         campaign.data.playsRemaining,
-        TOTAL_PLAYS - 2n, // two claims made so far
+        TOTAL_PLAYS - 3n, // three claims made so far
       );
     });
 
-    // Full timeout testing requires clock warping (bankrun / solana-program-test);
-    // localnet doesn't support it, so we only assert the play record state here.
-  });
-
-  describe("Close Campaign", () => {
-    const CLOSE_CAMPAIGN_ID = RUN_SEED + 1000n;
-    let closeCampaignPDA: Address;
+    test("cranker times out the play and collects the reward and rent", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 2_000));
 
-    before(async () => {
-      ({ pda: closeCampaignPDA } = await getPDAAndBump(
-        SAMIZDAT_PROGRAM_ADDRESS,
-        ["campaign", publisherAccountPDA, CLOSE_CAMPAIGN_ID],
-      ));
+      const config = await fetchProtocolConfig(
+        connection.rpc,
+        protocolConfigPDA,
+      );
+      const play = await fetchPlayRecord(connection.rpc, timeoutPlayRecordPDA);
+      const bond = play.data.bondAmount;
+      const reward = (bond * BigInt(config.data.crankRewardBps)) / 10_000n;
+      // Record lamports = rent + escrowed bond
+      const recordLamports =
+        await connection.getLamportBalance(timeoutPlayRecordPDA);
+      const campaignBefore = await fetchCampaignAccount(
+        connection.rpc,
+        campaignAccountPDA,
+      );
+      const nodeBefore = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      const campaignLamportsBefore =
+        await connection.getLamportBalance(campaignAccountPDA);
+      const crankerBefore = await connection.getLamportBalance(
+        publisher.address,
+        "confirmed",
+      );
 
-      // Create a small campaign specifically for closing
-      const createIx = await getCreateCampaignInstructionAsync({
-        authority: publisher,
-        campaignId: CLOSE_CAMPAIGN_ID,
-        cids: SAMPLE_CIDS,
-        bountyPerPlay: 1_000n,
-        totalPlays: 1n,
-        tagMask: 0n,
-        targetFilters: SAMPLE_TARGET_FILTERS,
-        claimCooldown: 0n,
+      // Publisher cranks; operator pays the transaction fee
+      const ix = await getTimeoutPlayInstructionAsync({
+        playRecord: timeoutPlayRecordPDA,
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        operator: operator.address,
+        cranker: publisher,
       });
-
       await connection.sendTransactionFromInstructions({
-        feePayer: publisher,
-        instructions: [createIx],
+        feePayer: operator,
+        instructions: [ix],
       });
-    });
 
-    test("closes a campaign and reclaims rent + remaining funds", async () => {
-      const publisherBalanceBefore = await connection.getLamportBalance(
+      const crankerAfter = await connection.getLamportBalance(
         publisher.address,
+        "confirmed",
+      );
+      // Cranker keeps the rent and its share of the bond; the rest of the
+      // bond goes to the campaign, and nothing leaves the campaign vault
+      assert.strictEqual(
+        crankerAfter - crankerBefore,
+        recordLamports - (bond - reward),
+      );
+      assert.strictEqual(
+        (await connection.getLamportBalance(campaignAccountPDA)) -
+          campaignLamportsBefore,
+        bond - reward,
+      );
+      assert.strictEqual(
+        await connection.getLamportBalance(timeoutPlayRecordPDA),
+        0n,
       );
 
-      const ix = await getCloseCampaignInstructionAsync({
-        campaignAccount: closeCampaignPDA,
-        authority: publisher,
+      const campaignAfter = await fetchCampaignAccount(
+        connection.rpc,
+        campaignAccountPDA,
+      );
+      assert.strictEqual(
+        campaignAfter.data.playsRemaining,
+        campaignBefore.data.playsRemaining + 1n,
+      );
+      assert.strictEqual(
+        campaignAfter.data.reservedBudget,
+        campaignBefore.data.reservedBudget - campaignBefore.data.bountyPerPlay,
+      );
+      assert.strictEqual(
+        campaignAfter.data.outstandingClaims,
+        campaignBefore.data.outstandingClaims - 1n,
+      );
+
+      // Forfeited bond is recorded against the node
+      const nodeAfter = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.strictEqual(
+        nodeAfter.data.bondsForfeited,
+        nodeBefore.data.bondsForfeited + (bond > 0n ? 1n : 0n),
+      );
+
+      // Timeout is counted against the node's reputation
+      assert.strictEqual(
+        nodeAfter.data.timedOutPlays,
+        nodeBefore.data.timedOutPlays + 1n,
+      );
+      assert.ok(nodeAfter.data.reputationBps < nodeBefore.data.reputationBps);
+    });
+  });
+
+  describe("Protocol Config", () => {
+    const PAUSED_NONCE = 5n;
+    const FEE_SKIPPED_NONCE = 4n;
+
+    test("non-admin cannot update the config", async () => {
+      const ix = await getUpdateProtocolConfigInstructionAsync({
+        admin: publisher,
+        treasury: null,
+        feeBps: null,
+        crankRewardBps: null,
+        claimBond: null,
+        playTimeout: null,
+        paused: true,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__UNAUTHORIZED),
+      );
+    });
+
+    test("rejects a fee above 100%", async () => {
+      const ix = await getUpdateProtocolConfigInstructionAsync({
+        admin,
+        treasury: null,
+        feeBps: 10_001,
+        crankRewardBps: null,
+        claimBond: null,
+        playTimeout: null,
+        paused: null,
       });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: admin,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_FEE),
+      );
+    });
 
+    test("pausing the protocol blocks new claims", async () => {
+      const pauseIx = await getUpdateProtocolConfigInstructionAsync({
+        admin,
+        treasury: null,
+        feeBps: null,
+        crankRewardBps: null,
+        claimBond: null,
+        playTimeout: null,
+        paused: true,
+      });
       await connection.sendTransactionFromInstructions({
-        feePayer: publisher,
-        instructions: [ix],
+        feePayer: admin,
+        instructions: [pauseIx],
       });
 
-      // Campaign account should be closed (zero lamports)
+      const claimIx = await getClaimCampaignInstructionAsync({
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: PAUSED_NONCE,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [claimIx],
+        }),
+        programError(SAMIZDAT_ERROR__PROTOCOL_PAUSED),
+      );
+
+      const resumeIx = await getUpdateProtocolConfigInstructionAsync({
+        admin,
+        treasury: null,
+        feeBps: null,
+        crankRewardBps: null,
+        claimBond: null,
+        playTimeout: null,
+        paused: false,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [resumeIx],
+      });
+
+      const config = await fetchProtocolConfig(
+        connection.rpc,
+        protocolConfigPDA,
+      );
+      assert.strictEqual(config.data.paused, false);
+    });
+
+    test("admin transfer takes effect only once accepted", async () => {
+      const proposeIx = await getTransferProtocolAdminInstructionAsync({
+        admin,
+        newAdmin: publisher.address,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [proposeIx],
+      });
+
+      let config = await fetchProtocolConfig(connection.rpc, protocolConfigPDA);
+      assert.strictEqual(config.data.admin, admin.address);
+      assert.deepStrictEqual(config.data.pendingAdmin, {
+        __option: "Some",
+        value: publisher.address,
+      });
+
+      // Someone other than the pending admin cannot accept
+      const hijackIx = await getAcceptProtocolAdminInstructionAsync({
+        pendingAdmin: operator,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [hijackIx],
+        }),
+        programError(SAMIZDAT_ERROR__UNAUTHORIZED),
+      );
+
+      const acceptIx = await getAcceptProtocolAdminInstructionAsync({
+        pendingAdmin: publisher,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [acceptIx],
+      });
+
+      config = await fetchProtocolConfig(connection.rpc, protocolConfigPDA);
+      assert.strictEqual(config.data.admin, publisher.address);
+      assert.deepStrictEqual(config.data.pendingAdmin, { __option: "None" });
+
+      // Hand the role back so later runs keep the provider wallet as admin
+      const returnIx = await getTransferProtocolAdminInstructionAsync({
+        admin: publisher,
+        newAdmin: admin.address,
+      });
+      const reacceptIx = await getAcceptProtocolAdminInstructionAsync({
+        pendingAdmin: admin,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [returnIx],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [reacceptIx],
+      });
+
+      config = await fetchProtocolConfig(connection.rpc, protocolConfigPDA);
+      assert.strictEqual(config.data.admin, admin.address);
+    });
+
+    test("waives a fee the treasury cannot receive", async () => {
+      const setTreasury = (newTreasury: Address) =>
+        getUpdateProtocolConfigInstructionAsync({
+          admin,
+          treasury: newTreasury,
+          feeBps: null,
+          crankRewardBps: null,
+          claimBond: null,
+          playTimeout: null,
+          paused: null,
+        });
+      // An empty wallet cannot hold a fee below the rent-exempt minimum
+      const emptyTreasury = await getAddressFromPublicKey(
+        (await generateKeyPair()).publicKey,
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [await setTreasury(emptyTreasury)],
+      });
+
+      const { pda: playRecord } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", campaignAccountPDA, nodeAccountPDA, FEE_SKIPPED_NONCE],
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getClaimCampaignInstructionAsync({
+            campaignAccount: campaignAccountPDA,
+            publisherAccount: publisherAccountPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            creativeIndex: CREATIVE_INDEX,
+            claimNonce: FEE_SKIPPED_NONCE,
+            allowlistProof: null,
+          }),
+        ],
+      });
+      const claimed = await fetchPlayRecord(connection.rpc, playRecord);
+      const campaignLamportsBefore =
+        await connection.getLamportBalance(campaignAccountPDA);
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await ed25519ProofInstruction(
+            renderer,
+            proofMessage(
+              campaignAccountPDA,
+              nodeAccountPDA,
+              FEE_SKIPPED_NONCE,
+              claimed.data.claimedAt,
+            ),
+          ),
+          await getConfirmPlayInstructionAsync({
+            playRecord,
+            campaignAccount: campaignAccountPDA,
+            publisherAccount: publisherAccountPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            treasury: emptyTreasury,
+            proofTimestamp: claimed.data.claimedAt,
+            durationMs: null,
+          }),
+        ],
+      });
+
+      const payout = BOUNTY_PER_PLAY - feeFor(BOUNTY_PER_PLAY);
+      const paid = await fetchPlayRecord(connection.rpc, playRecord);
+      assert.strictEqual(paid.data.status, PlayStatus.Paid);
+      assert.strictEqual(paid.data.paymentAmount, payout);
+      assert.strictEqual(paid.data.feeAmount, 0n);
+      assert.strictEqual(await connection.getLamportBalance(emptyTreasury), 0n);
+      // Only the operator's payout leaves the campaign; the fee stays in it
+      assert.strictEqual(
+        campaignLamportsBefore -
+          (await connection.getLamportBalance(campaignAccountPDA)),
+        payout,
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [await setTreasury(treasury)],
+      });
+    });
+  });
+
+  describe("Moderation", () => {
+    const SUSPENDED_NONCE = 6n;
+    const PRE_SUSPENSION_NONCE = 7n;
+    const SUSPENSION_REASON = 7;
+
+    const claimIx = () =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount: campaignAccountPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: SUSPENDED_NONCE,
+        allowlistProof: null,
+      });
+
+    test("non-admin cannot suspend a node", async () => {
+      const ix = await getSuspendNodeInstructionAsync({
+        admin: publisher,
+        nodeAccount: nodeAccountPDA,
+        reason: SUSPENSION_REASON,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__UNAUTHORIZED),
+      );
+    });
+
+    test("operator cannot suspend their own node", async () => {
+      const ix = await getUpdateNodeMetadataInstructionAsync({
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        location: null,
+        estimatedFootfall: null,
+        blockedTagMask: null,
+        requiredTagMask: null,
+        minBountyPerPlay: null,
+        requiresApproval: null,
+        status: NodeStatus.Suspended,
+        signingKey: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_STATUS_TRANSITION),
+      );
+    });
+
+    test("suspended node cannot claim or lift its own suspension", async () => {
+      const suspendIx = await getSuspendNodeInstructionAsync({
+        admin,
+        nodeAccount: nodeAccountPDA,
+        reason: SUSPENSION_REASON,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [suspendIx],
+      });
+
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.strictEqual(node.data.status, NodeStatus.Suspended);
+      assert.strictEqual(node.data.suspensionReason, SUSPENSION_REASON);
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx()],
+        }),
+        programError(SAMIZDAT_ERROR__NODE_NOT_ACTIVE),
+      );
+
+      const liftIx = await getUpdateNodeMetadataInstructionAsync({
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        location: null,
+        estimatedFootfall: null,
+        blockedTagMask: null,
+        requiredTagMask: null,
+        minBountyPerPlay: null,
+        requiresApproval: null,
+        status: NodeStatus.Active,
+        signingKey: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [liftIx],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_STATUS_TRANSITION),
+      );
+
+      const reinstateIx = await getReinstateNodeInstructionAsync({
+        admin,
+        nodeAccount: nodeAccountPDA,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [reinstateIx],
+      });
+
+      const reinstated = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.strictEqual(reinstated.data.status, NodeStatus.Active);
+      assert.strictEqual(reinstated.data.suspensionReason, 0);
+    });
+
+    test("suspended publisher's campaigns cannot be claimed", async () => {
+      // Claimed before the suspension, confirmed after reinstatement
+      const { pda: playRecord } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        [
+          "play_record",
+          campaignAccountPDA,
+          nodeAccountPDA,
+          PRE_SUSPENSION_NONCE,
+        ],
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getClaimCampaignInstructionAsync({
+            campaignAccount: campaignAccountPDA,
+            publisherAccount: publisherAccountPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            creativeIndex: CREATIVE_INDEX,
+            claimNonce: PRE_SUSPENSION_NONCE,
+            allowlistProof: null,
+          }),
+        ],
+      });
+
+      const suspendIx = await getSuspendPublisherInstructionAsync({
+        admin,
+        publisherAccount: publisherAccountPDA,
+        reason: SUSPENSION_REASON,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [suspendIx],
+      });
+
+      const pub = await fetchPublisherAccount(
+        connection.rpc,
+        publisherAccountPDA,
+      );
+      assert.strictEqual(pub.data.status, PublisherStatus.Suspended);
+      assert.strictEqual(pub.data.suspensionReason, SUSPENSION_REASON);
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx()],
+        }),
+        programError(SAMIZDAT_ERROR__PUBLISHER_NOT_ACTIVE),
+      );
+
+      // The earlier claim is not paid while the publisher is suspended
+      const claimed = await fetchPlayRecord(connection.rpc, playRecord);
+      const confirmInstructions = async () => [
+        await ed25519ProofInstruction(
+          renderer,
+          proofMessage(
+            campaignAccountPDA,
+            nodeAccountPDA,
+            PRE_SUSPENSION_NONCE,
+            claimed.data.claimedAt,
+          ),
+        ),
+        await getConfirmPlayInstructionAsync({
+          playRecord,
+          campaignAccount: campaignAccountPDA,
+          publisherAccount: publisherAccountPDA,
+          nodeAccount: nodeAccountPDA,
+          authority: operator,
+          treasury,
+          proofTimestamp: claimed.data.claimedAt,
+          durationMs: null,
+        }),
+      ];
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: await confirmInstructions(),
+        }),
+        programError(SAMIZDAT_ERROR__PUBLISHER_NOT_ACTIVE),
+      );
+
+      const reinstateIx = await getReinstatePublisherInstructionAsync({
+        admin,
+        publisherAccount: publisherAccountPDA,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [reinstateIx],
+      });
+
+      const reinstated = await fetchPublisherAccount(
+        connection.rpc,
+        publisherAccountPDA,
+      );
+      assert.strictEqual(reinstated.data.status, PublisherStatus.Active);
+
+      // Once reinstated, the claim confirms within its timeout
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: await confirmInstructions(),
+      });
+      const paid = await fetchPlayRecord(connection.rpc, playRecord);
+      assert.strictEqual(paid.data.status, PlayStatus.Paid);
+    });
+
+    test("non-admin cannot record a lost dispute", async () => {
+      const ix = await getRecordLostDisputeInstructionAsync({
+        admin: publisher,
+        nodeAccount: nodeAccountPDA,
+        reason: SUSPENSION_REASON,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__UNAUTHORIZED),
+      );
+    });
+
+    test("lost dispute lowers node reputation", async () => {
+      const before = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+
+      const ix = await getRecordLostDisputeInstructionAsync({
+        admin,
+        nodeAccount: nodeAccountPDA,
+        reason: SUSPENSION_REASON,
+      });
+      const signature = await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [ix],
+      });
+
+      const [disputed] = await getEmittedEvents(
+        connection,
+        signature,
+        "NodeDisputeLost",
+      );
+      assert.ok(disputed, "NodeDisputeLost not emitted");
+      assert.strictEqual(eventAddress(disputed, 0), nodeAccountPDA);
+
+      const after = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.strictEqual(
+        after.data.disputesLost,
+        before.data.disputesLost + 1n,
+      );
+      assert.strictEqual(
+        after.data.reputationBps,
+        Math.floor((before.data.reputationBps * 9500) / 10000),
+      );
+    });
+
+    test("campaign min_reputation excludes low-reputation nodes", async () => {
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      const setMinReputation = (minReputation: number | null) =>
+        getUpdateCampaignInstructionAsync({
+          campaignAccount: campaignAccountPDA,
+          authority: publisher,
+          tagMask: null,
+          targetFilters: { ...SAMPLE_TARGET_FILTERS, minReputation },
+          status: null,
+          startAt: null,
+          endAt: null,
+          dailyPlayCap: null,
+          bountyPerPlay: null,
+        });
+
+      // Out-of-range threshold is rejected
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await setMinReputation(10_001)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_REPUTATION),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setMinReputation(node.data.reputationBps + 1)],
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx()],
+        }),
+        programError(SAMIZDAT_ERROR__TARGET_MISMATCH),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setMinReputation(null)],
+      });
+    });
+  });
+
+  describe("Campaign Flight Dates", () => {
+    const FUTURE_CAMPAIGN_ID = RUN_SEED + 4000n;
+    const ENDING_CAMPAIGN_ID = RUN_SEED + 4001n;
+    let futureCampaignPDA: Address;
+    let endingCampaignPDA: Address;
+
+    const nowSeconds = () => BigInt(Math.floor(Date.now() / 1000));
+
+    before(async () => {
+      ({ pda: futureCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, FUTURE_CAMPAIGN_ID],
+      ));
+      ({ pda: endingCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, ENDING_CAMPAIGN_ID],
+      ));
+
+      const futureIx = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: FUTURE_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 1n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: nowSeconds() + 3600n,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+      // Listed in the GeoIndex of cell dr5r until it expires
+      const aroundNode: GeoRegionArgs = {
+        __kind: "Circle",
+        center: SAMPLE_LOCATION,
+        radiusM: 1_000,
+      };
+      const endingIx = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: ENDING_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 1n,
+        tagMask: 0n,
+        targetFilters: { ...SAMPLE_TARGET_FILTERS, geoRegions: [aroundNode] },
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: nowSeconds() + 5n,
+        dailyPlayCap: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [futureIx, await withGeoIndexes(endingIx, ["dr5r"])],
+      });
+    });
+
+    test("rejects claims before the flight starts", async () => {
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: futureCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__CAMPAIGN_NOT_STARTED),
+      );
+    });
+
+    test("removing the flight start opens the campaign", async () => {
+      const updateIx = await getUpdateCampaignInstructionAsync({
+        campaignAccount: futureCampaignPDA,
+        authority: publisher,
+        tagMask: null,
+        targetFilters: null,
+        status: null,
+        startAt: some(null),
+        endAt: null,
+        dailyPlayCap: null,
+        bountyPerPlay: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [updateIx],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        futureCampaignPDA,
+      );
+      assert.deepStrictEqual(campaign.data.startAt, { __option: "None" });
+
+      const claimIx = await getClaimCampaignInstructionAsync({
+        campaignAccount: futureCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [claimIx],
+      });
+    });
+
+    test("anyone can expire a campaign after its flight ends", async () => {
+      const expireIx = async () =>
+        withGeoIndexes(
+          await getExpireCampaignInstructionAsync({
+            campaignAccount: endingCampaignPDA,
+            publisherAccount: publisherAccountPDA,
+            authority: publisher.address,
+          }),
+          ["dr5r"],
+        );
+
+      // Too early: flight still running
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await expireIx()],
+        }),
+        programError(SAMIZDAT_ERROR__FLIGHT_NOT_ENDED),
+      );
+
+      await new Promise((resolve) => setTimeout(resolve, 7_000));
+
+      const claimIx = await getClaimCampaignInstructionAsync({
+        campaignAccount: endingCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [claimIx],
+        }),
+        programError(SAMIZDAT_ERROR__CAMPAIGN_ENDED),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await expireIx()],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        endingCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.status, CampaignStatus.Expired);
+      assert.deepStrictEqual(campaign.data.geoCells, []);
+
+      // Dropped from discovery
+      const index = await fetchMaybeGeoIndex(
+        connection.rpc,
+        await geoIndexAddress("dr5r"),
+      );
+      assert.ok(
+        !index.exists || !index.data.campaigns.includes(endingCampaignPDA),
+      );
+    });
+  });
+
+  describe("Dayparting", () => {
+    const DAYPART_CAMPAIGN_ID = RUN_SEED + 5000n;
+    let daypartCampaignPDA: Address;
+
+    const closedAllWeek = new Uint8Array(21);
+    const openAllWeek = new Uint8Array(21).fill(0xff);
+
+    before(async () => {
+      ({ pda: daypartCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, DAYPART_CAMPAIGN_ID],
+      ));
+
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: DAYPART_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 2n,
+        tagMask: 0n,
+        targetFilters: {
+          ...SAMPLE_TARGET_FILTERS,
+          daypart: { hours: closedAllWeek, utcOffsetMinutes: -300 },
+        },
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+    });
+
+    test("rejects claims outside the scheduled hours", async () => {
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: daypartCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__OUTSIDE_DAYPART),
+      );
+    });
+
+    test("accepts claims once the hour is scheduled", async () => {
+      const updateIx = await getUpdateCampaignInstructionAsync({
+        campaignAccount: daypartCampaignPDA,
+        authority: publisher,
+        tagMask: null,
+        targetFilters: {
+          ...SAMPLE_TARGET_FILTERS,
+          daypart: { hours: openAllWeek, utcOffsetMinutes: -300 },
+        },
+        status: null,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+        bountyPerPlay: null,
+      });
+      const claimIx = await getClaimCampaignInstructionAsync({
+        campaignAccount: daypartCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [updateIx],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [claimIx],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        daypartCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.playsRemaining, 1n);
+    });
+  });
+
+  describe("Geo Regions", () => {
+    const GEO_CAMPAIGN_ID = RUN_SEED + 12_000n;
+    let geoCampaignPDA: Address;
+
+    // ~5 km north of the node, and a box around central London
+    const NEARBY_STORE: GeoRegionArgs = {
+      __kind: "Circle",
+      center: {
+        latitude: SAMPLE_LOCATION.latitude + 450_000n,
+        longitude: SAMPLE_LOCATION.longitude,
+      },
+      radiusM: 2_000,
+    };
+    const LONDON: GeoRegionArgs = {
+      __kind: "Polygon",
+      vertices: [
+        { latitude: 514_500_000n, longitude: -2_500_000n },
+        { latitude: 514_500_000n, longitude: 500_000n },
+        { latitude: 515_500_000n, longitude: 500_000n },
+        { latitude: 515_500_000n, longitude: -2_500_000n },
+      ],
+    };
+
+    // GeoIndex cells of [NEARBY_STORE, LONDON]; widening the store's
+    // circle to 6 km adds dr72
+    const GEO_CELLS = ["dr5r", "gcpu", "gcpv", "u10h", "u10j"];
+
+    const createIx = (campaignId: bigint, geoRegions: GeoRegionArgs[]) =>
+      getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 2n,
+        tagMask: 0n,
+        targetFilters: { ...SAMPLE_TARGET_FILTERS, geoRegions },
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+
+    const claimIx = () =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount: geoCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+
+    before(async () => {
+      ({ pda: geoCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, GEO_CAMPAIGN_ID],
+      ));
+    });
+
+    test("rejects malformed regions", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [
+            await createIx(GEO_CAMPAIGN_ID, [{ ...NEARBY_STORE, radiusM: 0 }]),
+          ],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_GEO_REGION),
+      );
+    });
+
+    test("rejects nodes outside every region", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await withGeoIndexes(
+            await createIx(GEO_CAMPAIGN_ID, [NEARBY_STORE, LONDON]),
+            GEO_CELLS,
+          ),
+        ],
+      });
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx()],
+        }),
+        programError(SAMIZDAT_ERROR__TARGET_MISMATCH),
+      );
+    });
+
+    test("accepts nodes inside any region", async () => {
+      const updateIx = await getUpdateCampaignInstructionAsync({
+        campaignAccount: geoCampaignPDA,
+        authority: publisher,
+        tagMask: null,
+        targetFilters: {
+          ...SAMPLE_TARGET_FILTERS,
+          geoRegions: [LONDON, { ...NEARBY_STORE, radiusM: 6_000 }],
+        },
+        status: null,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+        bountyPerPlay: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await withGeoIndexes(updateIx, ["dr72"])],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx()],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        geoCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.outstandingClaims, 1n);
+      assert.deepStrictEqual(
+        campaign.data.geoCells,
+        [...GEO_CELLS, "dr72"].sort(),
+      );
+    });
+  });
+
+  describe("Geo Index", () => {
+    const INDEXED_CAMPAIGN_ID = RUN_SEED + 13_000n;
+    const GLOBAL_CAMPAIGN_ID = RUN_SEED + 13_001n;
+    let indexedCampaignPDA: Address;
+
+    // 1 km around the node, inside geohash cell dr5r
+    const AROUND_NODE: GeoRegionArgs = {
+      __kind: "Circle",
+      center: SAMPLE_LOCATION,
+      radiusM: 1_000,
+    };
+
+    const indexedCampaigns = async (cell: string) => {
+      const index = await fetchMaybeGeoIndex(
+        connection.rpc,
+        await geoIndexAddress(cell),
+      );
+      return index.exists ? index.data.campaigns : [];
+    };
+
+    before(async () => {
+      ({ pda: indexedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, INDEXED_CAMPAIGN_ID],
+      ));
+    });
+
+    test("lists geo-targeted campaigns in their cell", async () => {
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: INDEXED_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 1n,
+        tagMask: 0n,
+        targetFilters: { ...SAMPLE_TARGET_FILTERS, geoRegions: [AROUND_NODE] },
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+
+      // Without its GeoIndex the campaign cannot be created
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_GEO_INDEX),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await withGeoIndexes(ix, ["dr5r"])],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        indexedCampaignPDA,
+      );
+      assert.deepStrictEqual(campaign.data.geoCells, ["dr5r"]);
+      assert.ok((await indexedCampaigns("dr5r")).includes(indexedCampaignPDA));
+    });
+
+    test("drops closed campaigns from their cell", async () => {
+      const ix = await getCloseCampaignInstructionAsync({
+        campaignAccount: indexedCampaignPDA,
+        authority: publisher,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await withGeoIndexes(ix, ["dr5r"])],
+      });
+
+      assert.ok(
+        !(await indexedCampaigns("dr5r")).includes(indexedCampaignPDA),
+      );
+    });
+
+    test("lists very wide campaigns in the global cell", async () => {
+      const { pda: globalCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, GLOBAL_CAMPAIGN_ID],
+      );
+      // Most of the Americas
+      const americas: GeoRegionArgs = {
+        __kind: "Rect",
+        bounds: {
+          minLat: -550_000_000n,
+          maxLat: 700_000_000n,
+          minLon: -1_650_000_000n,
+          maxLon: -350_000_000n,
+        },
+      };
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: GLOBAL_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 1n,
+        tagMask: 0n,
+        targetFilters: { ...SAMPLE_TARGET_FILTERS, geoRegions: [americas] },
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await withGeoIndexes(ix, [""])],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        globalCampaignPDA,
+      );
+      assert.deepStrictEqual(campaign.data.geoCells, [""]);
+      assert.ok((await indexedCampaigns("")).includes(globalCampaignPDA));
+
+      const closeIx = await getCloseCampaignInstructionAsync({
+        campaignAccount: globalCampaignPDA,
+        authority: publisher,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await withGeoIndexes(closeIx, [""])],
+      });
+      assert.ok(!(await indexedCampaigns("")).includes(globalCampaignPDA));
+    });
+  });
+
+  describe("Node Lists", () => {
+    const LISTED_CAMPAIGN_ID = RUN_SEED + 14_000n;
+    let listedCampaignPDA: Address;
+    let denylistPDA: Address;
+
+    // Two-leaf allowlist: the operator, and another partner venue (any key)
+    let operatorLeaf: Uint8Array;
+    let partnerLeaf: Uint8Array;
+
+    const claimIx = (claimNonce: bigint, allowlistProof: Uint8Array[] | null) =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount: listedCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce,
+        allowlistProof,
+      });
+
+    before(async () => {
+      ({ pda: listedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, LISTED_CAMPAIGN_ID],
+      ));
+      ({ pda: denylistPDA } = await getPDAAndBump(SAMIZDAT_PROGRAM_ADDRESS, [
+        "node_denylist",
+        listedCampaignPDA,
+      ]));
+      operatorLeaf = allowlistLeaf(operator.address);
+      partnerLeaf = allowlistLeaf(publisherAccountPDA);
+
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: LISTED_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 3n,
+        tagMask: 0n,
+        targetFilters: {
+          ...SAMPLE_TARGET_FILTERS,
+          nodeAllowlist: allowlistParent(operatorLeaf, partnerLeaf),
+        },
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+    });
+
+    test("rejects claims without an allowlist proof", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(1n, null)],
+        }),
+        programError(SAMIZDAT_ERROR__NODE_NOT_ALLOWLISTED),
+      );
+      // The partner's proof does not prove this operator
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(1n, [operatorLeaf])],
+        }),
+        programError(SAMIZDAT_ERROR__NODE_NOT_ALLOWLISTED),
+      );
+    });
+
+    test("accepts nodes of an allowlisted operator", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(1n, [partnerLeaf])],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        listedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.outstandingClaims, 1n);
+    });
+
+    test("rejects denylisted nodes", async () => {
+      const ix = await getUpdateNodeDenylistInstructionAsync({
+        campaignAccount: listedCampaignPDA,
+        authority: publisher,
+        add: [nodeAccountPDA],
+        remove: [],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      const denylist = await fetchNodeDenylist(connection.rpc, denylistPDA);
+      assert.deepStrictEqual(denylist.data.entries, [nodeAccountPDA]);
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(2n, [partnerLeaf])],
+        }),
+        programError(SAMIZDAT_ERROR__NODE_DENYLISTED),
+      );
+    });
+
+    test("lifts the denylist on close", async () => {
+      const ix = await getCloseNodeDenylistInstructionAsync({
+        nodeDenylist: denylistPDA,
+        authority: publisher,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(2n, [partnerLeaf])],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        listedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.outstandingClaims, 2n);
+    });
+  });
+
+  describe("Creative Approval", () => {
+    const REVIEWED_CAMPAIGN_ID = RUN_SEED + 15_000n;
+    const CREATIVES = [
+      SAMPLE_CREATIVES[0],
+      sampleCreative(
+        "bafybeihkoviema7g3gxyt6la7vd5ho32ictqbilu3wnlo3rs7ewhnp7lly",
+      ),
+    ];
+    let reviewedCampaignPDA: Address;
+    let approvalPDA: Address;
+
+    const setRequiresApproval = (requiresApproval: boolean) =>
+      getUpdateNodeMetadataInstructionAsync({
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        location: null,
+        estimatedFootfall: null,
+        blockedTagMask: null,
+        requiredTagMask: null,
+        minBountyPerPlay: null,
+        requiresApproval,
+        status: null,
+        signingKey: null,
+      });
+
+    const claimIx = (
+      creativeIndex: number,
+      claimNonce: bigint,
+      approvedCampaign?: Address,
+    ) =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount: reviewedCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        approvedCampaign,
+        creativeIndex,
+        claimNonce,
+        allowlistProof: null,
+      });
+
+    before(async () => {
+      ({ pda: reviewedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, REVIEWED_CAMPAIGN_ID],
+      ));
+      [approvalPDA] = await getProgramDerivedAddress({
+        programAddress: SAMIZDAT_PROGRAM_ADDRESS,
+        seeds: [
+          "approved_campaign",
+          getAddressEncoder().encode(nodeAccountPDA),
+          getAddressEncoder().encode(reviewedCampaignPDA),
+          CREATIVES[0].contentHash,
+        ],
+      });
+
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: REVIEWED_CAMPAIGN_ID,
+        creatives: CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 3n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setRequiresApproval(true)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+    });
+
+    after(async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setRequiresApproval(false)],
+      });
+    });
+
+    test("rejects claims the operator has not approved", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(0, 1n)],
+        }),
+        programError(SAMIZDAT_ERROR__CAMPAIGN_NOT_APPROVED),
+      );
+    });
+
+    test("approval covers only the approved creative", async () => {
+      const ix = await getApproveCampaignInstructionAsync({
+        approvedCampaign: approvalPDA,
+        nodeAccount: nodeAccountPDA,
+        campaignAccount: reviewedCampaignPDA,
+        authority: operator,
+        contentHash: CREATIVES[0].contentHash,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [ix],
+      });
+
+      const approval = await fetchApprovedCampaign(connection.rpc, approvalPDA);
+      assert.strictEqual(approval.data.campaignAccount, reviewedCampaignPDA);
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(1, 1n, approvalPDA)],
+        }),
+        programError(SAMIZDAT_ERROR__CAMPAIGN_NOT_APPROVED),
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(0, 1n, approvalPDA)],
+      });
+    });
+
+    test("an approved hash cannot be reused by another creative", async () => {
+      const ix = await getAddCreativesToCampaignInstructionAsync({
+        campaignAccount: reviewedCampaignPDA,
+        authority: publisher,
+        newCreatives: [
+          {
+            ...sampleCreative(
+              "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+            ),
+            contentHash: CREATIVES[0].contentHash,
+          },
+        ],
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__DUPLICATE_CREATIVE),
+      );
+    });
+
+    test("rejects claims once the approval is revoked", async () => {
+      const ix = await getRevokeCampaignApprovalInstructionAsync({
+        approvedCampaign: approvalPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [ix],
+      });
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(0, 2n, approvalPDA)],
+        }),
+        programError(ACCOUNT_NOT_INITIALIZED),
+      );
+    });
+  });
+
+  describe("Publisher Frequency Cap", () => {
+    const FIRST_CAMPAIGN_ID = RUN_SEED + 16_000n;
+    const SECOND_CAMPAIGN_ID = RUN_SEED + 16_001n;
+    let firstCampaignPDA: Address;
+    let secondCampaignPDA: Address;
+
+    const setLimits = (
+      nodeCooldown: bigint,
+      frequencyCap: { maxPlays: number; windowSeconds: bigint } | null,
+    ) =>
+      getUpdateFrequencyCapInstructionAsync({
+        authority: publisher,
+        nodeCooldown,
+        frequencyCap,
+      });
+
+    const claimIx = (campaignAccount: Address) =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+
+    before(async () => {
+      ({ pda: firstCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, FIRST_CAMPAIGN_ID],
+      ));
+      ({ pda: secondCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, SECOND_CAMPAIGN_ID],
+      ));
+
+      const createIxs = await Promise.all(
+        [FIRST_CAMPAIGN_ID, SECOND_CAMPAIGN_ID].map((campaignId) =>
+          getCreateCampaignInstructionAsync({
+            authority: publisher,
+            campaignId,
+            creatives: SAMPLE_CREATIVES,
+            bountyPerPlay: 1_000n,
+            pricing: PER_PLAY,
+            totalPlays: 2n,
+            tagMask: 0n,
+            targetFilters: SAMPLE_TARGET_FILTERS,
+            claimCooldown: 0n,
+            startAt: null,
+            endAt: null,
+            dailyPlayCap: null,
+          }),
+        ),
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: createIxs,
+      });
+    });
+
+    after(async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setLimits(0n, null)],
+      });
+    });
+
+    test("caps plays across the publisher's campaigns", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await setLimits(0n, { maxPlays: 1, windowSeconds: 3600n }),
+        ],
+      });
+      const account = await fetchPublisherAccount(
+        connection.rpc,
+        publisherAccountPDA,
+      );
+      assert.deepStrictEqual(account.data.frequencyCap, {
+        __option: "Some",
+        value: { maxPlays: 1, windowSeconds: 3600n },
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(firstCampaignPDA)],
+      });
+      // Another campaign of the same publisher counts against the same cap
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(secondCampaignPDA)],
+        }),
+        programError(SAMIZDAT_ERROR__FREQUENCY_CAP_REACHED),
+      );
+    });
+
+    test("publisher cooldown spaces plays on a node", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setLimits(3600n, null)],
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(secondCampaignPDA)],
+        }),
+        programError(SAMIZDAT_ERROR__COOLDOWN_NOT_EXPIRED),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setLimits(0n, null)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(secondCampaignPDA)],
+      });
+    });
+
+    test("rejects a node cooldown over 30 days", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await setLimits(30n * 86_400n + 1n, null)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_AMOUNT),
+      );
+    });
+  });
+
+  describe("Daily Pacing", () => {
+    const PACED_CAMPAIGN_ID = RUN_SEED + 6000n;
+    let pacedCampaignPDA: Address;
+
+    before(async () => {
+      ({ pda: pacedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, PACED_CAMPAIGN_ID],
+      ));
+
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: PACED_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 10n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: 1n,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+    });
+
+    test("stops claims once the daily cap is reached", async () => {
+      const claim = (nonce: bigint) =>
+        getClaimCampaignInstructionAsync({
+          campaignAccount: pacedCampaignPDA,
+          publisherAccount: publisherAccountPDA,
+          nodeAccount: nodeAccountPDA,
+          authority: operator,
+          creativeIndex: CREATIVE_INDEX,
+          claimNonce: nonce,
+          allowlistProof: null,
+        });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claim(1n)],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        pacedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.playsToday, 1n);
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claim(2n)],
+        }),
+        programError(SAMIZDAT_ERROR__DAILY_CAP_REACHED),
+      );
+    });
+
+    test("rejects a zero cap; removing the cap resumes claims", async () => {
+      const setCap = (dailyPlayCap: bigint | ReturnType<typeof some<null>>) =>
+        getUpdateCampaignInstructionAsync({
+          campaignAccount: pacedCampaignPDA,
+          authority: publisher,
+          tagMask: null,
+          targetFilters: null,
+          status: null,
+          startAt: null,
+          endAt: null,
+          dailyPlayCap,
+          bountyPerPlay: null,
+        });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await setCap(0n)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_PLAYS),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setCap(some(null))],
+      });
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        pacedCampaignPDA,
+      );
+      assert.deepStrictEqual(campaign.data.dailyPlayCap, { __option: "None" });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getClaimCampaignInstructionAsync({
+            campaignAccount: pacedCampaignPDA,
+            publisherAccount: publisherAccountPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            creativeIndex: CREATIVE_INDEX,
+            claimNonce: 2n,
+            allowlistProof: null,
+          }),
+        ],
+      });
+    });
+  });
+
+  describe("Per-Second Pricing", () => {
+    const PER_SECOND_CAMPAIGN_ID = RUN_SEED + 7000n;
+    const PRICE_PER_SECOND = 1_000n;
+    const MAX_DURATION_MS = 20_000;
+    // Most one play can cost: 20s at 1000 lamports/s
+    const MAX_PAYOUT = 20_000n;
+    const PER_SECOND: PricingModelArgs = {
+      __kind: "PerSecond",
+      pricePerSecond: PRICE_PER_SECOND,
+      maxDurationMs: MAX_DURATION_MS,
+    };
+    const NONCE = 1n;
+    let perSecondCampaignPDA: Address;
+    let perSecondPlayRecordPDA: Address;
+
+    const createIx = (bountyPerPlay: bigint) =>
+      getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: PER_SECOND_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay,
+        pricing: PER_SECOND,
+        totalPlays: 2n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+
+    const confirmIx = (durationMs: number, proofTimestamp: bigint) =>
+      getConfirmPlayInstructionAsync({
+        playRecord: perSecondPlayRecordPDA,
+        campaignAccount: perSecondCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        proofTimestamp,
+        durationMs,
+      });
+
+    before(async () => {
+      ({ pda: perSecondCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, PER_SECOND_CAMPAIGN_ID],
+      ));
+      ({ pda: perSecondPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", perSecondCampaignPDA, nodeAccountPDA, NONCE],
+      ));
+    });
+
+    test("bounty must equal the maximum per-play payout", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await createIx(MAX_PAYOUT + 1n)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_BOUNTY),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await createIx(MAX_PAYOUT)],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        perSecondCampaignPDA,
+      );
+      assert.deepStrictEqual(campaign.data.pricing, PER_SECOND);
+      assert.strictEqual(campaign.data.reservedBudget, 0n);
+    });
+
+    test("claim reserves budget instead of a play slot", async () => {
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: perSecondCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: NONCE,
+        allowlistProof: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [ix],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        perSecondCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, MAX_PAYOUT);
+      assert.strictEqual(campaign.data.playsRemaining, 2n);
+    });
+
+    test("rejects a duration longer than the creative", async () => {
+      const play = await fetchPlayRecord(
+        connection.rpc,
+        perSecondPlayRecordPDA,
+      );
+      // SAMPLE_CREATIVES[0] declares 15s
+      const durationMs = 16_000;
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessageV2(
+          perSecondCampaignPDA,
+          nodeAccountPDA,
+          NONCE,
+          play.data.claimedAt,
+          durationMs,
+        ),
+      );
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [
+            proofIx,
+            await confirmIx(durationMs, play.data.claimedAt),
+          ],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_DURATION),
+      );
+    });
+
+    test("rejects a duration that pays under the node's floor", async () => {
+      const setFloor = async (minBountyPerPlay: bigint) =>
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [
+            await getUpdateNodeMetadataInstructionAsync({
+              nodeAccount: nodeAccountPDA,
+              authority: operator,
+              location: null,
+              estimatedFootfall: null,
+              blockedTagMask: null,
+              requiredTagMask: null,
+              minBountyPerPlay,
+              requiresApproval: null,
+              status: null,
+              signingKey: null,
+            }),
+          ],
+        });
+      const play = await fetchPlayRecord(
+        connection.rpc,
+        perSecondPlayRecordPDA,
+      );
+      // 12.5s pays 12_500, under the floor a full 15s play would clear
+      const durationMs = 12_500;
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessageV2(
+          perSecondCampaignPDA,
+          nodeAccountPDA,
+          NONCE,
+          play.data.claimedAt,
+          durationMs,
+        ),
+      );
+
+      await setFloor(13_000n);
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [
+            proofIx,
+            await confirmIx(durationMs, play.data.claimedAt),
+          ],
+        }),
+        programError(SAMIZDAT_ERROR__BOUNTY_BELOW_NODE_FLOOR),
+      );
+      await setFloor(0n);
+    });
+
+    test("pays pro-rata for the proven duration", async () => {
+      const play = await fetchPlayRecord(
+        connection.rpc,
+        perSecondPlayRecordPDA,
+      );
+      const durationMs = 12_500;
+      const bounty = (PRICE_PER_SECOND * BigInt(durationMs)) / 1_000n;
+      const fee = feeFor(bounty);
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessageV2(
+          perSecondCampaignPDA,
+          nodeAccountPDA,
+          NONCE,
+          play.data.claimedAt,
+          durationMs,
+        ),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          proofIx,
+          await confirmIx(durationMs, play.data.claimedAt),
+        ],
+      });
+
+      const paid = await fetchPlayRecord(
+        connection.rpc,
+        perSecondPlayRecordPDA,
+      );
+      assert.strictEqual(paid.data.status, PlayStatus.Paid);
+      assert.strictEqual(paid.data.durationMs, durationMs);
+      assert.strictEqual(paid.data.paymentAmount, bounty - fee);
+      assert.strictEqual(paid.data.feeAmount, fee);
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        perSecondCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, 0n);
+    });
+  });
+
+  describe("CPM Pricing", () => {
+    const CPM_CAMPAIGN_ID = RUN_SEED + 8000n;
+    const CPM_RATE = 5_000n; // lamports per thousand impressions
+    const CEILING = 60_000n;
+    const NONCE = 1n;
+    let cpmCampaignPDA: Address;
+    let cpmPlayRecordPDA: Address;
+
+    const createIx = (cpmRate: bigint) =>
+      getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: CPM_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: CEILING,
+        pricing: { __kind: "Cpm", cpmRate },
+        totalPlays: 2n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+
+    before(async () => {
+      ({ pda: cpmCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, CPM_CAMPAIGN_ID],
+      ));
+      ({ pda: cpmPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", cpmCampaignPDA, nodeAccountPDA, NONCE],
+      ));
+    });
+
+    test("rejects a zero CPM rate", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await createIx(0n)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_PRICING),
+      );
+    });
+
+    test("pays by node footfall, capped at the ceiling", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await createIx(CPM_RATE)],
+      });
+
+      const claimIx = await getClaimCampaignInstructionAsync({
+        campaignAccount: cpmCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: NONCE,
+        allowlistProof: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [claimIx],
+      });
+
+      const reserved = await fetchCampaignAccount(
+        connection.rpc,
+        cpmCampaignPDA,
+      );
+      assert.strictEqual(reserved.data.reservedBudget, CEILING);
+
+      // The price uses the footfall recorded at claim, so raising it before
+      // confirming does not raise the payout
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      const setFootfall = (estimatedFootfall: number) =>
+        getUpdateNodeMetadataInstructionAsync({
+          nodeAccount: nodeAccountPDA,
+          authority: operator,
+          location: null,
+          estimatedFootfall,
+          blockedTagMask: null,
+          requiredTagMask: null,
+          minBountyPerPlay: null,
+          requiresApproval: null,
+          status: null,
+          signingKey: null,
+        });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setFootfall(1_000_000)],
+      });
+
+      const play = await fetchPlayRecord(connection.rpc, cpmPlayRecordPDA);
+      assert.strictEqual(play.data.footfall, node.data.estimatedFootfall);
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessage(
+          cpmCampaignPDA,
+          nodeAccountPDA,
+          NONCE,
+          play.data.claimedAt,
+        ),
+      );
+      const confirmIx = await getConfirmPlayInstructionAsync({
+        playRecord: cpmPlayRecordPDA,
+        campaignAccount: cpmCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        proofTimestamp: play.data.claimedAt,
+        durationMs: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [proofIx, confirmIx],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setFootfall(node.data.estimatedFootfall)],
+      });
+
+      let bounty = (CPM_RATE * BigInt(node.data.estimatedFootfall)) / 1_000n;
+      if (bounty > CEILING) bounty = CEILING;
+      const fee = feeFor(bounty);
+
+      const paid = await fetchPlayRecord(connection.rpc, cpmPlayRecordPDA);
+      assert.strictEqual(paid.data.status, PlayStatus.Paid);
+      assert.strictEqual(paid.data.paymentAmount, bounty - fee);
+      assert.strictEqual(paid.data.feeAmount, fee);
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        cpmCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, 0n);
+    });
+
+    test("a new ceiling applies to later claims only", async () => {
+      const LOWER_CEILING = CEILING / 2n;
+      const SECOND_NONCE = 2n;
+      const setCeiling = (campaignAccount: Address, bountyPerPlay: bigint) =>
+        getUpdateCampaignInstructionAsync({
+          campaignAccount,
+          authority: publisher,
+          tagMask: null,
+          targetFilters: null,
+          status: null,
+          startAt: null,
+          endAt: null,
+          dailyPlayCap: null,
+          bountyPerPlay,
+        });
+
+      // Only Cpm campaigns have a ceiling to change
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await setCeiling(campaignAccountPDA, LOWER_CEILING)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_PRICING),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setCeiling(cpmCampaignPDA, LOWER_CEILING)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getClaimCampaignInstructionAsync({
+            campaignAccount: cpmCampaignPDA,
+            publisherAccount: publisherAccountPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            creativeIndex: CREATIVE_INDEX,
+            claimNonce: SECOND_NONCE,
+            allowlistProof: null,
+          }),
+        ],
+      });
+      const { pda: playRecord } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", cpmCampaignPDA, nodeAccountPDA, SECOND_NONCE],
+      );
+      const play = await fetchPlayRecord(connection.rpc, playRecord);
+      assert.strictEqual(play.data.reservedAmount, LOWER_CEILING);
+      let campaign = await fetchCampaignAccount(connection.rpc, cpmCampaignPDA);
+      assert.strictEqual(campaign.data.reservedBudget, LOWER_CEILING);
+
+      // Raising the ceiling again does not raise the outstanding claim
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setCeiling(cpmCampaignPDA, CEILING)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await ed25519ProofInstruction(
+            renderer,
+            proofMessage(
+              cpmCampaignPDA,
+              nodeAccountPDA,
+              SECOND_NONCE,
+              play.data.claimedAt,
+            ),
+          ),
+          await getConfirmPlayInstructionAsync({
+            playRecord,
+            campaignAccount: cpmCampaignPDA,
+            publisherAccount: publisherAccountPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            treasury,
+            proofTimestamp: play.data.claimedAt,
+            durationMs: null,
+          }),
+        ],
+      });
+
+      let bounty = (CPM_RATE * BigInt(play.data.footfall)) / 1_000n;
+      if (bounty > LOWER_CEILING) bounty = LOWER_CEILING;
+      const paid = await fetchPlayRecord(connection.rpc, playRecord);
+      assert.strictEqual(paid.data.paymentAmount, bounty - feeFor(bounty));
+      campaign = await fetchCampaignAccount(connection.rpc, cpmCampaignPDA);
+      assert.strictEqual(campaign.data.bountyPerPlay, CEILING);
+      assert.strictEqual(campaign.data.reservedBudget, 0n);
+    });
+  });
+
+  describe("Slot Auctions", () => {
+    const WINNER_CAMPAIGN_ID = RUN_SEED + 9000n;
+    const LOSER_CAMPAIGN_ID = RUN_SEED + 9001n;
+    const SILENT_CAMPAIGN_ID = RUN_SEED + 9002n;
+    const DENIED_CAMPAIGN_ID = RUN_SEED + 9003n;
+    const SLOT_ID = RUN_SEED;
+    const RELEASED_SLOT_ID = RUN_SEED + 1n;
+    const BOUNTY = 50_000n;
+    const RESERVE_PRICE = 10_000n;
+    const BID_DEPOSIT = 1_000_000n;
+    const WINNING_BID = 40_000n;
+    const LOSING_BID = 25_000n;
+    const NONCE = 1n;
+    const SALT = new Uint8Array(32).fill(7);
+    let winnerCampaignPDA: Address;
+    let loserCampaignPDA: Address;
+    let silentCampaignPDA: Address;
+    let deniedCampaignPDA: Address;
+    let slotAuctionPDA: Address;
+    let auctionPlayRecordPDA: Address;
+
+    const nowSeconds = () => BigInt(Math.floor(Date.now() / 1000));
+
+    const bidPDA = async (campaign: Address, slotAuction = slotAuctionPDA) =>
+      (
+        await getPDAAndBump(SAMIZDAT_PROGRAM_ADDRESS, [
+          "bid",
+          slotAuction,
+          campaign,
+        ])
+      ).pda;
+
+    const commitIx = (
+      campaignAccount: Address,
+      amount: bigint,
+      slotAuction = slotAuctionPDA,
+    ) =>
+      getCommitBidInstructionAsync({
+        slotAuction,
+        nodeAccount: nodeAccountPDA,
+        campaignAccount,
+        authority: publisher,
+        commitment: bidCommitment(amount, SALT, campaignAccount),
+        allowlistProof: null,
+      });
+
+    const revealIx = async (
+      campaignAccount: Address,
+      amount: bigint,
+      salt = SALT,
+      slotAuction = slotAuctionPDA,
+    ) =>
+      getRevealBidInstructionAsync({
+        bid: await bidPDA(campaignAccount, slotAuction),
+        slotAuction,
+        campaignAccount,
+        authority: publisher,
+        amount,
+        salt,
+      });
+
+    const claimIx = (
+      campaignAccount: Address,
+      slotAuction: Address | undefined = slotAuctionPDA,
+    ) =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        slotAuction,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: NONCE,
+        allowlistProof: null,
+      });
+
+    before(async () => {
+      ({ pda: winnerCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, WINNER_CAMPAIGN_ID],
+      ));
+      ({ pda: loserCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, LOSER_CAMPAIGN_ID],
+      ));
+      ({ pda: silentCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, SILENT_CAMPAIGN_ID],
+      ));
+      ({ pda: deniedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, DENIED_CAMPAIGN_ID],
+      ));
+      ({ pda: slotAuctionPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["slot_auction", nodeAccountPDA, SLOT_ID],
+      ));
+      ({ pda: auctionPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", winnerCampaignPDA, nodeAccountPDA, NONCE],
+      ));
+
+      const createIxs = await Promise.all(
+        [
+          WINNER_CAMPAIGN_ID,
+          LOSER_CAMPAIGN_ID,
+          SILENT_CAMPAIGN_ID,
+          DENIED_CAMPAIGN_ID,
+        ].map(
+          (campaignId) =>
+            getCreateCampaignInstructionAsync({
+              authority: publisher,
+              campaignId,
+              creatives: SAMPLE_CREATIVES,
+              bountyPerPlay: BOUNTY,
+              pricing: PER_PLAY,
+              totalPlays: 1n,
+              tagMask: 0n,
+              targetFilters: SAMPLE_TARGET_FILTERS,
+              claimCooldown: 0n,
+              startAt: null,
+              endAt: null,
+              dailyPlayCap: null,
+            }),
+        ),
+      );
+      for (const ix of createIxs) {
+        await connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [ix],
+        });
+      }
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await getUpdateNodeDenylistInstructionAsync({
+            campaignAccount: deniedCampaignPDA,
+            authority: publisher,
+            add: [nodeAccountPDA],
+            remove: [],
+          }),
+        ],
+      });
+    });
+
+    test("operator opens an auction for a slot", async () => {
+      const commitEndsAt = nowSeconds() + 5n;
+      const revealEndsAt = commitEndsAt + 5n;
+      const ix = await getOpenSlotAuctionInstructionAsync({
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        slotId: SLOT_ID,
+        commitEndsAt,
+        revealEndsAt,
+        slotStart: revealEndsAt,
+        slotEnd: revealEndsAt + 3600n,
+        reservePrice: RESERVE_PRICE,
+        bidDeposit: BID_DEPOSIT,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [ix],
+      });
+
+      const auction = await fetchSlotAuction(connection.rpc, slotAuctionPDA);
+      assert.strictEqual(auction.data.status, AuctionStatus.Open);
+      assert.strictEqual(auction.data.reservePrice, RESERVE_PRICE);
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.deepStrictEqual(node.data.auctionedSlots, [
+        {
+          slotAuction: slotAuctionPDA,
+          slotStart: revealEndsAt,
+          slotEnd: revealEndsAt + 3600n,
+        },
+      ]);
+    });
+
+    test("bids are committed as hashes with a deposit", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await commitIx(loserCampaignPDA, LOSING_BID),
+          await commitIx(winnerCampaignPDA, WINNING_BID),
+          // Above the bounty: can never be revealed
+          await commitIx(silentCampaignPDA, BOUNTY + 1n),
+        ],
+      });
+
+      const auction = await fetchSlotAuction(connection.rpc, slotAuctionPDA);
+      assert.strictEqual(auction.data.bidCount, 3);
+      assert.strictEqual(auction.data.highestBid, 0n);
+
+      const bid = await fetchBid(
+        connection.rpc,
+        await bidPDA(winnerCampaignPDA),
+      );
+      assert.strictEqual(bid.data.deposit, BID_DEPOSIT);
+      assert.strictEqual(bid.data.revealed, false);
+
+      // A campaign that could not claim the node cannot bid for it either
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await commitIx(deniedCampaignPDA, LOSING_BID)],
+        }),
+        programError(SAMIZDAT_ERROR__NODE_DENYLISTED),
+      );
+    });
+
+    test("rejects reveals before the commit window closes", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await revealIx(winnerCampaignPDA, WINNING_BID)],
+        }),
+        programError(SAMIZDAT_ERROR__REVEAL_NOT_OPEN),
+      );
+    });
+
+    test("reveals track the top two bids and return deposits", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 6_000));
+
+      // Too late to commit, even for a campaign that has not bid yet
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await commitIx(campaignAccountPDA, BOUNTY)],
+        }),
+        programError(SAMIZDAT_ERROR__BIDDING_CLOSED),
+      );
+
+      // Wrong amount or salt
+      for (const ix of [
+        await revealIx(winnerCampaignPDA, BOUNTY),
+        await revealIx(winnerCampaignPDA, WINNING_BID, new Uint8Array(32)),
+      ]) {
+        await assert.rejects(
+          connection.sendTransactionFromInstructions({
+            feePayer: publisher,
+            instructions: [ix],
+          }),
+          programError(SAMIZDAT_ERROR__COMMITMENT_MISMATCH),
+        );
+      }
+
+      // Matches its commitment but exceeds the bounty
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await revealIx(silentCampaignPDA, BOUNTY + 1n)],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_BID),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await revealIx(loserCampaignPDA, LOSING_BID),
+          await revealIx(winnerCampaignPDA, WINNING_BID),
+        ],
+      });
+
+      const auction = await fetchSlotAuction(connection.rpc, slotAuctionPDA);
+      assert.strictEqual(auction.data.revealedBids, 2);
+      assert.strictEqual(auction.data.highestBid, WINNING_BID);
+      assert.strictEqual(auction.data.secondBid, LOSING_BID);
+      assert.deepStrictEqual(auction.data.winner, {
+        __option: "Some",
+        value: winnerCampaignPDA,
+      });
+
+      const bid = await fetchBid(
+        connection.rpc,
+        await bidPDA(winnerCampaignPDA),
+      );
+      assert.strictEqual(bid.data.amount, WINNING_BID);
+      assert.strictEqual(bid.data.deposit, 0n);
+    });
+
+    test("settles at the second-highest bid once reveals close", async () => {
+      const settleIx = () =>
+        getSettleSlotAuctionInstructionAsync({
+          slotAuction: slotAuctionPDA,
+          nodeAccount: nodeAccountPDA,
+          cranker: operator,
+        });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await settleIx()],
+        }),
+        programError(SAMIZDAT_ERROR__BIDDING_NOT_CLOSED),
+      );
+
+      await new Promise((resolve) => setTimeout(resolve, 6_000));
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await settleIx()],
+      });
+
+      const auction = await fetchSlotAuction(connection.rpc, slotAuctionPDA);
+      assert.strictEqual(auction.data.status, AuctionStatus.Settled);
+      assert.strictEqual(auction.data.clearingPrice, LOSING_BID);
+    });
+
+    test("only the winning campaign can claim the slot", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(loserCampaignPDA)],
+        }),
+        programError(SAMIZDAT_ERROR__NOT_AUCTION_WINNER),
+      );
+
+      // Leaving the auction out does not get around the sold slot
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(loserCampaignPDA, undefined)],
+        }),
+        programError(SAMIZDAT_ERROR__SLOT_AUCTIONED),
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(winnerCampaignPDA)],
+      });
+
+      const auction = await fetchSlotAuction(connection.rpc, slotAuctionPDA);
+      assert.strictEqual(auction.data.status, AuctionStatus.Claimed);
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.deepStrictEqual(node.data.auctionedSlots, []);
+      const play = await fetchPlayRecord(connection.rpc, auctionPlayRecordPDA);
+      assert.deepStrictEqual(play.data.fixedPrice, {
+        __option: "Some",
+        value: LOSING_BID,
+      });
+    });
+
+    test("pays the clearing price on confirm", async () => {
+      const play = await fetchPlayRecord(connection.rpc, auctionPlayRecordPDA);
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessage(
+          winnerCampaignPDA,
+          nodeAccountPDA,
+          NONCE,
+          play.data.claimedAt,
+        ),
+      );
+      const confirmIx = await getConfirmPlayInstructionAsync({
+        playRecord: auctionPlayRecordPDA,
+        campaignAccount: winnerCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        proofTimestamp: play.data.claimedAt,
+        durationMs: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [proofIx, confirmIx],
+      });
+
+      const fee = feeFor(LOSING_BID);
+      const paid = await fetchPlayRecord(connection.rpc, auctionPlayRecordPDA);
+      assert.strictEqual(paid.data.status, PlayStatus.Paid);
+      assert.strictEqual(paid.data.paymentAmount, LOSING_BID - fee);
+      assert.strictEqual(paid.data.feeAmount, fee);
+    });
+
+    test("unrevealed bids forfeit half their deposit on close", async () => {
+      const closeAuctionIx = () =>
+        getCloseSlotAuctionInstructionAsync({
+          slotAuction: slotAuctionPDA,
+          nodeAccount: nodeAccountPDA,
+          authority: operator,
+        });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await closeAuctionIx()],
+        }),
+        programError(SAMIZDAT_ERROR__AUCTION_NOT_FINISHED),
+      );
+
+      const auctionBefore = await connection.getLamportBalance(slotAuctionPDA);
+      const bids = await Promise.all(
+        [winnerCampaignPDA, loserCampaignPDA, silentCampaignPDA].map(bidPDA),
+      );
+      const closeBidIxs = await Promise.all(
+        bids.map((bid) =>
+          getCloseBidInstructionAsync({
+            bid,
+            slotAuction: slotAuctionPDA,
+            authority: publisher,
+          }),
+        ),
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: closeBidIxs,
+      });
+      assert.strictEqual(
+        await connection.getLamportBalance(slotAuctionPDA),
+        auctionBefore + BID_DEPOSIT / 2n,
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await closeAuctionIx()],
+      });
+      assert.strictEqual(
+        await connection.getLamportBalance(slotAuctionPDA),
+        0n,
+      );
+    });
+
+    test("releases a slot its winner can no longer claim", async () => {
+      const { pda: releasedAuctionPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["slot_auction", nodeAccountPDA, RELEASED_SLOT_ID],
+      );
+      const commitEndsAt = nowSeconds() + 3n;
+      const revealEndsAt = commitEndsAt + 3n;
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getOpenSlotAuctionInstructionAsync({
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+            slotId: RELEASED_SLOT_ID,
+            commitEndsAt,
+            revealEndsAt,
+            slotStart: revealEndsAt,
+            slotEnd: revealEndsAt + 3600n,
+            reservePrice: RESERVE_PRICE,
+            bidDeposit: BID_DEPOSIT,
+          }),
+        ],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await commitIx(loserCampaignPDA, LOSING_BID, releasedAuctionPDA),
+        ],
+      });
+      await new Promise((resolve) => setTimeout(resolve, 4_000));
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await revealIx(
+            loserCampaignPDA,
+            LOSING_BID,
+            SALT,
+            releasedAuctionPDA,
+          ),
+        ],
+      });
+      await new Promise((resolve) => setTimeout(resolve, 4_000));
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getSettleSlotAuctionInstructionAsync({
+            slotAuction: releasedAuctionPDA,
+            nodeAccount: nodeAccountPDA,
+            cranker: operator,
+          }),
+        ],
+      });
+
+      const releaseIx = () =>
+        getReleaseAuctionedSlotInstructionAsync({
+          slotAuction: releasedAuctionPDA,
+          nodeAccount: nodeAccountPDA,
+          campaignAccount: loserCampaignPDA,
+          publisherAccount: publisherAccountPDA,
+          authority: operator,
+        });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await releaseIx()],
+        }),
+        programError(SAMIZDAT_ERROR__WINNER_CAN_STILL_CLAIM),
+      );
+
+      // Pausing the winner would otherwise leave the node's slot dead
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await getUpdateCampaignInstructionAsync({
+            campaignAccount: loserCampaignPDA,
+            authority: publisher,
+            tagMask: null,
+            targetFilters: null,
+            status: CampaignStatus.Paused,
+            startAt: null,
+            endAt: null,
+            dailyPlayCap: null,
+            bountyPerPlay: null,
+          }),
+        ],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await releaseIx()],
+      });
+
+      const auction = await fetchSlotAuction(
+        connection.rpc,
+        releasedAuctionPDA,
+      );
+      assert.strictEqual(auction.data.status, AuctionStatus.Released);
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.deepStrictEqual(node.data.auctionedSlots, []);
+
+      // A released auction can be closed before its slot ends
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await getCloseBidInstructionAsync({
+            bid: await bidPDA(loserCampaignPDA, releasedAuctionPDA),
+            slotAuction: releasedAuctionPDA,
+            authority: publisher,
+          }),
+        ],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getCloseSlotAuctionInstructionAsync({
+            slotAuction: releasedAuctionPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+          }),
+        ],
+      });
+    });
+  });
+
+  describe("Bookings", () => {
+    const BOOKED_CAMPAIGN_ID = RUN_SEED + 10_000n;
+    const OTHER_CAMPAIGN_ID = RUN_SEED + 10_001n;
+    const BOOKING_ID = 1n;
+    const CANCELLED_BOOKING_ID = 2n;
+    const GUARANTEED_PLAYS = 2n;
+    const PRICE_PER_PLAY = 30_000n;
+    const ESCROW = GUARANTEED_PLAYS * PRICE_PER_PLAY;
+    const NONCE = 1n;
+    let bookedCampaignPDA: Address;
+    let otherCampaignPDA: Address;
+    let bookingPDA: Address;
+    let bookedPlayRecordPDA: Address;
+    let startAt: bigint;
+    let endAt: bigint;
+
+    const nowSeconds = () => BigInt(Math.floor(Date.now() / 1000));
+
+    const createBookingIx = (bookingId: bigint, start: bigint, end: bigint) =>
+      getCreateBookingInstructionAsync({
+        campaignAccount: bookedCampaignPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: publisher,
+        bookingId,
+        startAt: start,
+        endAt: end,
+        guaranteedPlays: GUARANTEED_PLAYS,
+        pricePerPlay: PRICE_PER_PLAY,
+      });
+
+    const claimIx = (
+      campaignAccount: Address,
+      booking: Address | undefined,
+      claimNonce = NONCE,
+    ) =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        booking,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce,
+        allowlistProof: null,
+      });
+
+    before(async () => {
+      ({ pda: bookedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, BOOKED_CAMPAIGN_ID],
+      ));
+      ({ pda: otherCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, OTHER_CAMPAIGN_ID],
+      ));
+      ({ pda: bookingPDA } = await getPDAAndBump(SAMIZDAT_PROGRAM_ADDRESS, [
+        "booking",
+        bookedCampaignPDA,
+        nodeAccountPDA,
+        BOOKING_ID,
+      ]));
+      ({ pda: bookedPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", bookedCampaignPDA, nodeAccountPDA, NONCE],
+      ));
+
+      const createIxs = await Promise.all(
+        [BOOKED_CAMPAIGN_ID, OTHER_CAMPAIGN_ID].map((campaignId) =>
+          getCreateCampaignInstructionAsync({
+            authority: publisher,
+            campaignId,
+            creatives: SAMPLE_CREATIVES,
+            bountyPerPlay: 50_000n,
+            pricing: PER_PLAY,
+            totalPlays: 3n,
+            tagMask: 0n,
+            targetFilters: SAMPLE_TARGET_FILTERS,
+            claimCooldown: 0n,
+            startAt: null,
+            endAt: null,
+            dailyPlayCap: null,
+          }),
+        ),
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: createIxs,
+      });
+    });
+
+    test("rejects a booking window that has already started", async () => {
+      const now = nowSeconds();
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [
+            await createBookingIx(BOOKING_ID, now - 1n, now + 60n),
+          ],
+        }),
+        programError(SAMIZDAT_ERROR__INVALID_BOOKING),
+      );
+    });
+
+    test("publisher cancels a booking before it is accepted", async () => {
+      const now = nowSeconds();
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await createBookingIx(CANCELLED_BOOKING_ID, now + 60n, now + 120n),
+        ],
+      });
+      let campaign = await fetchCampaignAccount(
+        connection.rpc,
+        bookedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, ESCROW);
+
+      const { pda: cancelledBookingPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["booking", bookedCampaignPDA, nodeAccountPDA, CANCELLED_BOOKING_ID],
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [
+          await getCancelBookingInstructionAsync({
+            booking: cancelledBookingPDA,
+            campaignAccount: bookedCampaignPDA,
+            authority: publisher,
+          }),
+        ],
+      });
+
+      campaign = await fetchCampaignAccount(connection.rpc, bookedCampaignPDA);
+      assert.strictEqual(campaign.data.reservedBudget, 0n);
+      assert.strictEqual(campaign.data.openBookings, 0);
+    });
+
+    test("escrows the booking and reserves the node on accept", async () => {
+      startAt = nowSeconds() + 4n;
+      endAt = startAt + 15n;
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await createBookingIx(BOOKING_ID, startAt, endAt)],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        bookedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, ESCROW);
+      assert.strictEqual(campaign.data.openBookings, 1);
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [
+          await getAcceptBookingInstructionAsync({
+            booking: bookingPDA,
+            nodeAccount: nodeAccountPDA,
+            authority: operator,
+          }),
+        ],
+      });
+
+      const booking = await fetchBooking(connection.rpc, bookingPDA);
+      assert.strictEqual(booking.data.status, BookingStatus.Active);
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.ok(node.data.bookings.some((w) => w.booking === bookingPDA));
+    });
+
+    test("a booked node only claims the booked campaign", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 5_000));
+
+      for (const ix of [
+        await claimIx(otherCampaignPDA, undefined),
+        await claimIx(bookedCampaignPDA, undefined),
+      ]) {
+        await assert.rejects(
+          connection.sendTransactionFromInstructions({
+            feePayer: operator,
+            instructions: [ix],
+          }),
+          programError(SAMIZDAT_ERROR__NODE_BOOKED),
+        );
+      }
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(bookedCampaignPDA, bookingPDA)],
+      });
+
+      const play = await fetchPlayRecord(connection.rpc, bookedPlayRecordPDA);
+      assert.deepStrictEqual(play.data.fixedPrice, {
+        __option: "Some",
+        value: PRICE_PER_PLAY,
+      });
+      assert.deepStrictEqual(play.data.booking, {
+        __option: "Some",
+        value: bookingPDA,
+      });
+    });
+
+    test("pays the booked price from the escrow on confirm", async () => {
+      const play = await fetchPlayRecord(connection.rpc, bookedPlayRecordPDA);
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessage(
+          bookedCampaignPDA,
+          nodeAccountPDA,
+          NONCE,
+          play.data.claimedAt,
+        ),
+      );
+      const confirmIx = await getConfirmPlayInstructionAsync({
+        playRecord: bookedPlayRecordPDA,
+        campaignAccount: bookedCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        booking: bookingPDA,
+        proofTimestamp: play.data.claimedAt,
+        durationMs: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [proofIx, confirmIx],
+      });
+
+      const fee = feeFor(PRICE_PER_PLAY);
+      const paid = await fetchPlayRecord(connection.rpc, bookedPlayRecordPDA);
+      assert.strictEqual(paid.data.paymentAmount, PRICE_PER_PLAY - fee);
+
+      const booking = await fetchBooking(connection.rpc, bookingPDA);
+      assert.strictEqual(booking.data.playsDelivered, 1n);
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        bookedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, PRICE_PER_PLAY);
+      assert.strictEqual(campaign.data.playsRemaining, 3n);
+    });
+
+    test("a timed-out booked play frees its claim on the booking", async () => {
+      // Shorten the confirmation window so the claim expires quickly
+      const config = await fetchProtocolConfig(
+        connection.rpc,
+        protocolConfigPDA,
+      );
+      const setPlayTimeout = (playTimeout: bigint) =>
+        getUpdateProtocolConfigInstructionAsync({
+          admin,
+          treasury: null,
+          feeBps: null,
+          crankRewardBps: null,
+          claimBond: null,
+          playTimeout,
+          paused: null,
+        });
+      // Pausing the campaign after acceptance does not block booked claims
+      const setStatus = (status: CampaignStatus) =>
+        getUpdateCampaignInstructionAsync({
+          campaignAccount: bookedCampaignPDA,
+          authority: publisher,
+          tagMask: null,
+          targetFilters: null,
+          status,
+          startAt: null,
+          endAt: null,
+          dailyPlayCap: null,
+          bountyPerPlay: null,
+        });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setStatus(CampaignStatus.Paused)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [await setPlayTimeout(1n)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(bookedCampaignPDA, bookingPDA, 2n)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: admin,
+        instructions: [await setPlayTimeout(config.data.playTimeout)],
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await setStatus(CampaignStatus.Active)],
+      });
+      let booking = await fetchBooking(connection.rpc, bookingPDA);
+      assert.strictEqual(booking.data.playsClaimed, 2n);
+
+      await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+      const { pda: timedOutPlayPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", bookedCampaignPDA, nodeAccountPDA, 2n],
+      );
+      const timeoutIx = (booking: Address | undefined) =>
+        getTimeoutPlayInstructionAsync({
+          playRecord: timedOutPlayPDA,
+          campaignAccount: bookedCampaignPDA,
+          publisherAccount: publisherAccountPDA,
+          nodeAccount: nodeAccountPDA,
+          operator: operator.address,
+          booking,
+          cranker: publisher,
+        });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [await timeoutIx(undefined)],
+        }),
+        programError(SAMIZDAT_ERROR__BOOKING_MISMATCH),
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [await timeoutIx(bookingPDA)],
+      });
+
+      booking = await fetchBooking(connection.rpc, bookingPDA);
+      assert.strictEqual(booking.data.playsClaimed, 1n);
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        bookedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, PRICE_PER_PLAY);
+      assert.strictEqual(campaign.data.outstandingClaims, 0n);
+    });
+
+    test("settling refunds undelivered plays and frees the node", async () => {
+      const settleIx = () =>
+        getSettleBookingInstructionAsync({
+          booking: bookingPDA,
+          campaignAccount: bookedCampaignPDA,
+          nodeAccount: nodeAccountPDA,
+          authority: publisher.address,
+          cranker: operator,
+        });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await settleIx()],
+        }),
+        programError(SAMIZDAT_ERROR__BOOKING_NOT_ENDED),
+      );
+
+      const waitMs = Number(endAt - nowSeconds() + 1n) * 1000;
+      await new Promise((resolve) => setTimeout(resolve, waitMs));
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await settleIx()],
+      });
+
+      assert.strictEqual(await connection.getLamportBalance(bookingPDA), 0n);
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        bookedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.reservedBudget, 0n);
+      assert.strictEqual(campaign.data.openBookings, 0);
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.ok(!node.data.bookings.some((w) => w.booking === bookingPDA));
+    });
+  });
+
+  describe("Node Price Floor and Required Tags", () => {
+    const TAGGED_CAMPAIGN_ID = RUN_SEED + 11_000n;
+    const UNTAGGED_CAMPAIGN_ID = RUN_SEED + 11_001n;
+    const FAMILY_TAG = 1n << 5n;
+    const BOUNTY = 20_000n;
+    let taggedCampaignPDA: Address;
+    let untaggedCampaignPDA: Address;
+
+    const setNodePolicy = (
+      requiredTagMask: bigint | null,
+      minBountyPerPlay: bigint | null,
+    ) =>
+      getUpdateNodeMetadataInstructionAsync({
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        location: null,
+        estimatedFootfall: null,
+        blockedTagMask: null,
+        requiredTagMask,
+        minBountyPerPlay,
+        requiresApproval: null,
+        status: null,
+        signingKey: null,
+      });
+
+    const claimIx = (campaignAccount: Address) =>
+      getClaimCampaignInstructionAsync({
+        campaignAccount,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: 1n,
+        allowlistProof: null,
+      });
+
+    before(async () => {
+      ({ pda: taggedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, TAGGED_CAMPAIGN_ID],
+      ));
+      ({ pda: untaggedCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, UNTAGGED_CAMPAIGN_ID],
+      ));
+
+      const createIxs = await Promise.all(
+        [
+          [TAGGED_CAMPAIGN_ID, FAMILY_TAG],
+          [UNTAGGED_CAMPAIGN_ID, 0n],
+        ].map(([campaignId, tagMask]) =>
+          getCreateCampaignInstructionAsync({
+            authority: publisher,
+            campaignId,
+            creatives: SAMPLE_CREATIVES,
+            bountyPerPlay: BOUNTY,
+            pricing: PER_PLAY,
+            totalPlays: 2n,
+            tagMask,
+            targetFilters: SAMPLE_TARGET_FILTERS,
+            claimCooldown: 0n,
+            startAt: null,
+            endAt: null,
+            dailyPlayCap: null,
+          }),
+        ),
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: createIxs,
+      });
+    });
+
+    after(async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setNodePolicy(0n, 0n)],
+      });
+    });
+
+    test("operator sets a price floor and required tags", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setNodePolicy(FAMILY_TAG, BOUNTY + 1n)],
+      });
+
+      const node = await fetchNodeAccount(connection.rpc, nodeAccountPDA);
+      assert.strictEqual(node.data.requiredTagMask, FAMILY_TAG);
+      assert.strictEqual(node.data.minBountyPerPlay, BOUNTY + 1n);
+    });
+
+    test("rejects campaigns paying below the node's floor", async () => {
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(taggedCampaignPDA)],
+        }),
+        programError(SAMIZDAT_ERROR__BOUNTY_BELOW_NODE_FLOOR),
+      );
+    });
+
+    test("rejects campaigns without the required tags", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await setNodePolicy(null, BOUNTY)],
+      });
+
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(untaggedCampaignPDA)],
+        }),
+        programError(SAMIZDAT_ERROR__CONTENT_FILTER_VIOLATION),
+      );
+    });
+
+    test("claims a tagged campaign paying the floor", async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [await claimIx(taggedCampaignPDA)],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        taggedCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.outstandingClaims, 1n);
+    });
+  });
+
+  describe("Token Campaigns", () => {
+    const TOKEN_CAMPAIGN_ID = RUN_SEED + 2000n;
+    const TOKEN_BOUNTY = 1_000_000n; // 1 token at 6 decimals
+    const TOKEN_PLAYS = 5n;
+    let tokenMint: Address;
+    let tokenCampaignPDA: Address;
+    let tokenVault: Address;
+    let publisherTokenAccount: Address;
+
+    before(async () => {
+      tokenMint = await connection.createTokenMint({
+        mintAuthority: publisher,
+        decimals: 6,
+        name: "Samizdat Test USD",
+        symbol: "STUSD",
+        uri: "https://example.com/stusd.json",
+      });
+      await connection.mintTokens(
+        tokenMint,
+        publisher,
+        TOKEN_BOUNTY * TOKEN_PLAYS * 2n,
+        publisher.address,
+      );
+
+      ({ pda: tokenCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, TOKEN_CAMPAIGN_ID],
+      ));
+      tokenVault = await connection.getTokenAccountAddress(
+        tokenCampaignPDA,
+        tokenMint,
+        true,
+      );
+      publisherTokenAccount = await connection.getTokenAccountAddress(
+        publisher.address,
+        tokenMint,
+        true,
+      );
+    });
+
+    test("creates a token campaign funded into a PDA-owned vault", async () => {
+      const ix = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: TOKEN_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: TOKEN_BOUNTY,
+        pricing: PER_PLAY,
+        totalPlays: TOKEN_PLAYS,
+        tagMask: TAG_MASK,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: CLAIM_COOLDOWN,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+        mint: tokenMint,
+        vault: tokenVault,
+        authorityTokenAccount: publisherTokenAccount,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM_ADDRESS,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        tokenCampaignPDA,
+      );
+      assert.deepStrictEqual(campaign.data.mint, {
+        __option: "Some",
+        value: tokenMint,
+      });
+
+      const vaultBalance = await connection.getTokenAccountBalance({
+        tokenAccount: tokenVault,
+        useTokenExtensions: true,
+      });
+      assert.strictEqual(vaultBalance.amount, TOKEN_BOUNTY * TOKEN_PLAYS);
+    });
+
+    test("funds the token campaign vault", async () => {
+      const ix = await getFundCampaignInstructionAsync({
+        campaignAccount: tokenCampaignPDA,
+        authority: publisher,
+        amount: TOKEN_BOUNTY,
+        mint: tokenMint,
+        vault: tokenVault,
+        authorityTokenAccount: publisherTokenAccount,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM_ADDRESS,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      const vaultBalance = await connection.getTokenAccountBalance({
+        tokenAccount: tokenVault,
+        useTokenExtensions: true,
+      });
+      assert.strictEqual(
+        vaultBalance.amount,
+        TOKEN_BOUNTY * (TOKEN_PLAYS + 1n),
+      );
+    });
+
+    test("closing refunds the token vault to the publisher", async () => {
+      const ix = await getCloseCampaignInstructionAsync({
+        campaignAccount: tokenCampaignPDA,
+        authority: publisher,
+        mint: tokenMint,
+        vault: tokenVault,
+        authorityTokenAccount: publisherTokenAccount,
+        tokenProgram: TOKEN_EXTENSIONS_PROGRAM_ADDRESS,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      const publisherBalance = await connection.getTokenAccountBalance({
+        tokenAccount: publisherTokenAccount,
+        useTokenExtensions: true,
+      });
+      assert.strictEqual(
+        publisherBalance.amount,
+        TOKEN_BOUNTY * TOKEN_PLAYS * 2n,
+      );
+      assert.strictEqual(await connection.getLamportBalance(tokenVault), 0n);
+    });
+  });
+
+  for (const [label, tokenProgram, campaignId] of [
+    ["Token", TOKEN_PROGRAM_ADDRESS, RUN_SEED + 2001n],
+    ["Token-2022", TOKEN_EXTENSIONS_PROGRAM_ADDRESS, RUN_SEED + 2002n],
+  ] as const) {
+    describe(`${label} Campaign Payouts`, () => {
+      const TOKEN_BOUNTY = 1_000_000n; // 1 token at 6 decimals
+      const CONFIRMED_NONCE = 1n;
+      const TIMED_OUT_NONCE = 2n;
+      const useTokenExtensions = tokenProgram !== TOKEN_PROGRAM_ADDRESS;
+      let tokenMint: Address;
+      let tokenCampaignPDA: Address;
+      let tokenVault: Address;
+      let publisherTokenAccount: Address;
+      let operatorTokenAccount: Address;
+      let treasuryTokenAccount: Address;
+
+      const tokenBalance = async (tokenAccount: Address) =>
+        (
+          await connection.getTokenAccountBalance({
+            tokenAccount,
+            useTokenExtensions,
+          })
+        ).amount;
+
+      const playRecordAddress = async (nonce: bigint) =>
+        (
+          await getPDAAndBump(SAMIZDAT_PROGRAM_ADDRESS, [
+            "play_record",
+            tokenCampaignPDA,
+            nodeAccountPDA,
+            nonce,
+          ])
+        ).pda;
+
+      const claimIx = (claimNonce: bigint) =>
+        getClaimCampaignInstructionAsync({
+          campaignAccount: tokenCampaignPDA,
+          publisherAccount: publisherAccountPDA,
+          nodeAccount: nodeAccountPDA,
+          authority: operator,
+          creativeIndex: CREATIVE_INDEX,
+          claimNonce,
+          allowlistProof: null,
+          mint: tokenMint,
+          vault: tokenVault,
+          tokenProgram,
+        });
+
+      const setPlayTimeout = (playTimeout: bigint) =>
+        getUpdateProtocolConfigInstructionAsync({
+          admin,
+          treasury: null,
+          feeBps: null,
+          crankRewardBps: null,
+          claimBond: null,
+          playTimeout,
+          paused: null,
+        });
+
+      before(async () => {
+        // A plain mint under `tokenProgram`, with token accounts for the
+        // publisher, the operator and the treasury
+        const mint = await generateKeyPairSigner();
+        tokenMint = mint.address;
+        const space = BigInt(getMintSize());
+        const rent = await connection.rpc
+          .getMinimumBalanceForRentExemption(space)
+          .send();
+        [publisherTokenAccount, operatorTokenAccount, treasuryTokenAccount] =
+          await Promise.all(
+            [publisher.address, operator.address, treasury].map(
+              async (owner) =>
+                (
+                  await findAssociatedTokenPda({
+                    owner,
+                    tokenProgram,
+                    mint: tokenMint,
+                  })
+                )[0],
+            ),
+          );
+        await connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [
+            getCreateAccountInstruction({
+              payer: publisher,
+              newAccount: mint,
+              lamports: rent,
+              space,
+              programAddress: tokenProgram,
+            }),
+            getInitializeMint2Instruction(
+              {
+                mint: tokenMint,
+                decimals: 6,
+                mintAuthority: publisher.address,
+              },
+              { programAddress: tokenProgram },
+            ),
+            ...[
+              [publisher.address, publisherTokenAccount],
+              [operator.address, operatorTokenAccount],
+              [treasury, treasuryTokenAccount],
+            ].map(([owner, ata]) =>
+              getCreateAssociatedTokenIdempotentInstruction({
+                payer: publisher,
+                ata,
+                owner,
+                mint: tokenMint,
+                tokenProgram,
+              }),
+            ),
+            getMintToInstruction(
+              {
+                mint: tokenMint,
+                token: publisherTokenAccount,
+                mintAuthority: publisher,
+                amount: TOKEN_BOUNTY * 2n,
+              },
+              { programAddress: tokenProgram },
+            ),
+          ],
+        });
+
+        ({ pda: tokenCampaignPDA } = await getPDAAndBump(
+          SAMIZDAT_PROGRAM_ADDRESS,
+          ["campaign", publisherAccountPDA, campaignId],
+        ));
+        tokenVault = (
+          await findAssociatedTokenPda({
+            owner: tokenCampaignPDA,
+            tokenProgram,
+            mint: tokenMint,
+          })
+        )[0];
+        await connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [
+            await getCreateCampaignInstructionAsync({
+              authority: publisher,
+              campaignId,
+              creatives: SAMPLE_CREATIVES,
+              bountyPerPlay: TOKEN_BOUNTY,
+              pricing: PER_PLAY,
+              totalPlays: 2n,
+              tagMask: 0n,
+              targetFilters: SAMPLE_TARGET_FILTERS,
+              claimCooldown: 0n,
+              startAt: null,
+              endAt: null,
+              dailyPlayCap: null,
+              mint: tokenMint,
+              vault: tokenVault,
+              authorityTokenAccount: publisherTokenAccount,
+              tokenProgram,
+            }),
+          ],
+        });
+      });
+
+      test("pays a confirmed play and its fee in tokens", async () => {
+        await connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(CONFIRMED_NONCE)],
+        });
+
+        const playRecord = await playRecordAddress(CONFIRMED_NONCE);
+        const play = await fetchPlayRecord(connection.rpc, playRecord);
+        const proofIx = await ed25519ProofInstruction(
+          renderer,
+          proofMessage(
+            tokenCampaignPDA,
+            nodeAccountPDA,
+            CONFIRMED_NONCE,
+            play.data.claimedAt,
+          ),
+        );
+        const confirmIx = await getConfirmPlayInstructionAsync({
+          playRecord,
+          campaignAccount: tokenCampaignPDA,
+          publisherAccount: publisherAccountPDA,
+          nodeAccount: nodeAccountPDA,
+          authority: operator,
+          treasury,
+          proofTimestamp: play.data.claimedAt,
+          durationMs: null,
+          mint: tokenMint,
+          vault: tokenVault,
+          authorityTokenAccount: operatorTokenAccount,
+          treasuryTokenAccount,
+          tokenProgram,
+        });
+        await connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [proofIx, confirmIx],
+        });
+
+        const fee = feeFor(TOKEN_BOUNTY);
+        assert.strictEqual(
+          await tokenBalance(operatorTokenAccount),
+          TOKEN_BOUNTY - fee,
+        );
+        assert.strictEqual(await tokenBalance(treasuryTokenAccount), fee);
+        assert.strictEqual(await tokenBalance(tokenVault), TOKEN_BOUNTY);
+      });
+
+      test("a timed-out play leaves the tokens in the vault", async () => {
+        // A short confirmation window so the claim expires without clock
+        // warping
+        await connection.sendTransactionFromInstructions({
+          feePayer: admin,
+          instructions: [await setPlayTimeout(1n)],
+        });
+        await connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [await claimIx(TIMED_OUT_NONCE)],
+        });
+        await connection.sendTransactionFromInstructions({
+          feePayer: admin,
+          instructions: [await setPlayTimeout(300n)],
+        });
+        await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+        await connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [
+            await getTimeoutPlayInstructionAsync({
+              playRecord: await playRecordAddress(TIMED_OUT_NONCE),
+              campaignAccount: tokenCampaignPDA,
+              publisherAccount: publisherAccountPDA,
+              nodeAccount: nodeAccountPDA,
+              operator: operator.address,
+              cranker: publisher,
+            }),
+          ],
+        });
+
+        const campaign = await fetchCampaignAccount(
+          connection.rpc,
+          tokenCampaignPDA,
+        );
+        assert.strictEqual(campaign.data.outstandingClaims, 0n);
+        assert.strictEqual(campaign.data.reservedBudget, 0n);
+        assert.strictEqual(await tokenBalance(tokenVault), TOKEN_BOUNTY);
+        assert.strictEqual(
+          await tokenBalance(operatorTokenAccount),
+          TOKEN_BOUNTY - feeFor(TOKEN_BOUNTY),
+        );
+      });
+
+      test("closing refunds the unspent tokens", async () => {
+        await connection.sendTransactionFromInstructions({
+          feePayer: publisher,
+          instructions: [
+            await getCloseCampaignInstructionAsync({
+              campaignAccount: tokenCampaignPDA,
+              authority: publisher,
+              mint: tokenMint,
+              vault: tokenVault,
+              authorityTokenAccount: publisherTokenAccount,
+              tokenProgram,
+            }),
+          ],
+        });
+
+        assert.strictEqual(
+          await tokenBalance(publisherTokenAccount),
+          TOKEN_BOUNTY,
+        );
+        assert.strictEqual(await connection.getLamportBalance(tokenVault), 0n);
+        assert.strictEqual(
+          await connection.getLamportBalance(tokenCampaignPDA),
+          0n,
+        );
+      });
+    });
+  }
+
+  describe("Close Campaign", () => {
+    const CLOSE_CAMPAIGN_ID = RUN_SEED + 1000n;
+    let closeCampaignPDA: Address;
+
+    before(async () => {
+      ({ pda: closeCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, CLOSE_CAMPAIGN_ID],
+      ));
+
+      // Create a small campaign specifically for closing
+      const createIx = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: CLOSE_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 1n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [createIx],
+      });
+    });
+
+    test("closes a campaign and reclaims rent + remaining funds", async () => {
+      const publisherBalanceBefore = await connection.getLamportBalance(
+        publisher.address,
+      );
+
+      const ix = await getCloseCampaignInstructionAsync({
+        campaignAccount: closeCampaignPDA,
+        authority: publisher,
+      });
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      // Campaign account should be closed (zero lamports)
       const balance = await connection.getLamportBalance(closeCampaignPDA);
       assert.strictEqual(balance, 0n);
 
@@ -571,4 +4924,155 @@ describe("Samizdat Program – Happy Path", () => {
       );
     });
   });
+
+  describe("Close Campaign – Draining", () => {
+    const DRAIN_CAMPAIGN_ID = RUN_SEED + 3000n;
+    const DRAIN_NONCE = 1n;
+    let drainCampaignPDA: Address;
+    let drainPlayRecordPDA: Address;
+
+    before(async () => {
+      ({ pda: drainCampaignPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["campaign", publisherAccountPDA, DRAIN_CAMPAIGN_ID],
+      ));
+      ({ pda: drainPlayRecordPDA } = await getPDAAndBump(
+        SAMIZDAT_PROGRAM_ADDRESS,
+        ["play_record", drainCampaignPDA, nodeAccountPDA, DRAIN_NONCE],
+      ));
+
+      const createIx = await getCreateCampaignInstructionAsync({
+        authority: publisher,
+        campaignId: DRAIN_CAMPAIGN_ID,
+        creatives: SAMPLE_CREATIVES,
+        bountyPerPlay: 1_000n,
+        pricing: PER_PLAY,
+        totalPlays: 2n,
+        tagMask: 0n,
+        targetFilters: SAMPLE_TARGET_FILTERS,
+        claimCooldown: 0n,
+        startAt: null,
+        endAt: null,
+        dailyPlayCap: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [createIx],
+      });
+
+      const claimIx = await getClaimCampaignInstructionAsync({
+        campaignAccount: drainCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: DRAIN_NONCE,
+        allowlistProof: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [claimIx],
+      });
+    });
+
+    test("closing with an outstanding claim only drains the campaign", async () => {
+      const ix = await getCloseCampaignInstructionAsync({
+        campaignAccount: drainCampaignPDA,
+        authority: publisher,
+      });
+      const signature = await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [ix],
+      });
+
+      const campaign = await fetchCampaignAccount(
+        connection.rpc,
+        drainCampaignPDA,
+      );
+      assert.strictEqual(campaign.data.status, CampaignStatus.Draining);
+      assert.strictEqual(campaign.data.outstandingClaims, 1n);
+
+      // CampaignDraining { campaign, outstanding_claims, .. }
+      const [draining] = await getEmittedEvents(
+        connection,
+        signature,
+        "CampaignDraining",
+      );
+      assert.ok(draining, "CampaignDraining not emitted");
+      assert.strictEqual(eventAddress(draining, 0), drainCampaignPDA);
+      assert.strictEqual(eventU64(draining, 32), 1n);
+    });
+
+    test("rejects new claims while draining", async () => {
+      const ix = await getClaimCampaignInstructionAsync({
+        campaignAccount: drainCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        creativeIndex: CREATIVE_INDEX,
+        claimNonce: DRAIN_NONCE + 1n,
+        allowlistProof: null,
+      });
+      await assert.rejects(
+        connection.sendTransactionFromInstructions({
+          feePayer: operator,
+          instructions: [ix],
+        }),
+        programError(SAMIZDAT_ERROR__CAMPAIGN_NOT_ACTIVE),
+      );
+    });
+
+    test("outstanding claim can still be confirmed, then the campaign closes", async () => {
+      const play = await fetchPlayRecord(connection.rpc, drainPlayRecordPDA);
+      const proofIx = await ed25519ProofInstruction(
+        renderer,
+        proofMessage(
+          drainCampaignPDA,
+          nodeAccountPDA,
+          DRAIN_NONCE,
+          play.data.claimedAt,
+        ),
+      );
+      const confirmIx = await getConfirmPlayInstructionAsync({
+        playRecord: drainPlayRecordPDA,
+        campaignAccount: drainCampaignPDA,
+        publisherAccount: publisherAccountPDA,
+        nodeAccount: nodeAccountPDA,
+        authority: operator,
+        treasury,
+        proofTimestamp: play.data.claimedAt,
+        durationMs: null,
+      });
+      await connection.sendTransactionFromInstructions({
+        feePayer: operator,
+        instructions: [proofIx, confirmIx],
+      });
+
+      const paid = await fetchPlayRecord(connection.rpc, drainPlayRecordPDA);
+      assert.strictEqual(paid.data.status, PlayStatus.Paid);
+
+      const closeIx = await getCloseCampaignInstructionAsync({
+        campaignAccount: drainCampaignPDA,
+        authority: publisher,
+      });
+      const signature = await connection.sendTransactionFromInstructions({
+        feePayer: publisher,
+        instructions: [closeIx],
+      });
+
+      const balance = await connection.getLamportBalance(drainCampaignPDA);
+      assert.strictEqual(balance, 0n);
+
+      // CampaignClosed { campaign, publisher, refunded, .. }
+      const [closed] = await getEmittedEvents(
+        connection,
+        signature,
+        "CampaignClosed",
+      );
+      assert.ok(closed, "CampaignClosed not emitted");
+      assert.strictEqual(eventAddress(closed, 0), drainCampaignPDA);
+      assert.strictEqual(eventAddress(closed, 32), publisherAccountPDA);
+      assert.ok(eventU64(closed, 64) > 0n);
+    });
+  });
 });